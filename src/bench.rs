@@ -0,0 +1,252 @@
+//! Shared parts of benchmarks (used by `sort::bench`, `search::bench` and their binaries):
+//! timing statistics, command line parsing and writing results as table, CSV or JSON.
+
+use std::{fmt::Write, str::FromStr, time::Duration};
+
+/// Nearest-rank percentile of sorted (non empty) times.
+pub fn percentile(sorted: &[Duration], p: usize) -> Duration {
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+/// Output format of benchmark results.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Format {
+    Table,
+    Csv,
+    Json,
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Option<Format> {
+        match name {
+            "table" => Some(Format::Table),
+            "csv" => Some(Format::Csv),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Column of results table.
+#[derive(Clone, Copy, Debug)]
+pub struct Column {
+    pub name: &'static str,
+    /// Width of column in table format.
+    pub width: usize,
+    /// Numbers are right aligned in table and not quoted in JSON,
+    /// other values are left aligned and quoted.
+    pub numeric: bool,
+}
+
+impl Column {
+    pub const fn text(name: &'static str, width: usize) -> Self {
+        Column {
+            name,
+            width,
+            numeric: false,
+        }
+    }
+
+    pub const fn number(name: &'static str, width: usize) -> Self {
+        Column {
+            name,
+            width,
+            numeric: true,
+        }
+    }
+}
+
+/// Write rows of results (one value for every column) in given format.
+pub fn format_rows(columns: &[Column], rows: &[Vec<String>], format: Format) -> String {
+    match format {
+        Format::Table => format_table(columns, rows),
+        Format::Csv => format_csv(columns, rows),
+        Format::Json => format_json(columns, rows),
+    }
+}
+
+fn format_table(columns: &[Column], rows: &[Vec<String>]) -> String {
+    let line = |values: Vec<&str>| {
+        let cells: Vec<String> = columns
+            .iter()
+            .zip(values)
+            .map(|(column, value)| {
+                if column.numeric {
+                    format!("{value:>width$}", width = column.width)
+                } else {
+                    format!("{value:<width$}", width = column.width)
+                }
+            })
+            .collect();
+        cells.join(" ")
+    };
+
+    let mut out = String::new();
+    let _ = writeln!(out, "{}", line(columns.iter().map(|c| c.name).collect()));
+    for row in rows {
+        let _ = writeln!(out, "{}", line(row.iter().map(String::as_str).collect()));
+    }
+    out
+}
+
+fn format_csv(columns: &[Column], rows: &[Vec<String>]) -> String {
+    let names: Vec<&str> = columns.iter().map(|c| c.name).collect();
+    let mut out = names.join(",") + "\n";
+    for row in rows {
+        let _ = writeln!(out, "{}", row.join(","));
+    }
+    out
+}
+
+fn format_json(columns: &[Column], rows: &[Vec<String>]) -> String {
+    // values are numbers and static identifiers, so no escaping is needed
+    let rows: Vec<String> = rows
+        .iter()
+        .map(|row| {
+            let fields: Vec<String> = columns
+                .iter()
+                .zip(row)
+                .map(|(column, value)| {
+                    if column.numeric {
+                        format!("\"{}\": {value}", column.name)
+                    } else {
+                        format!("\"{}\": \"{value}\"", column.name)
+                    }
+                })
+                .collect();
+            format!("  {{{}}}", fields.join(", "))
+        })
+        .collect();
+    format!("[\n{}\n]\n", rows.join(",\n"))
+}
+
+/// Parse command line of benchmark binary: pairs of '--flag value' (or --help, which returns usage as Err).
+/// 'parse_flag' parses value of benchmark specific flag and returns Ok(false) if flag is not known,
+/// --format is parsed here and returned.
+pub fn parse_args<F>(args: &[String], usage: &str, mut parse_flag: F) -> Result<Format, String>
+where
+    F: FnMut(&str, &str) -> Result<bool, String>,
+{
+    let mut format = Format::Table;
+
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        if flag == "--help" || flag == "-h" {
+            return Err(usage.to_string());
+        }
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for {flag}"))?;
+        if flag == "--format" {
+            format = Format::from_name(value).ok_or_else(|| format!("unknown format: {value}"))?;
+        } else if !parse_flag(flag, value)? {
+            return Err(format!("unknown argument: {flag}"));
+        }
+    }
+
+    Ok(format)
+}
+
+pub fn parse_number<T: FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value for {flag}: {value}"))
+}
+
+/// Number which is at least 'min', e.g. number of measured runs (at least 1).
+pub fn parse_number_at_least<T>(flag: &str, value: &str, min: T) -> Result<T, String>
+where
+    T: FromStr + PartialOrd + std::fmt::Display,
+{
+    let number = parse_number(flag, value)?;
+    if number < min {
+        return Err(format!("value for {flag} must be at least {min}: {value}"));
+    }
+    Ok(number)
+}
+
+/// Comma separated list of values, e.g. '--sizes 100,1000'.
+pub fn parse_list<T, P>(value: &str, parse: P) -> Result<Vec<T>, String>
+where
+    P: Fn(&str) -> Result<T, String>,
+{
+    value.split(',').map(parse).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentile_works() {
+        let times: Vec<Duration> = (1..=20).map(Duration::from_nanos).collect();
+        assert_eq!(percentile(&times, 50), Duration::from_nanos(10));
+        assert_eq!(percentile(&times, 95), Duration::from_nanos(19));
+        assert_eq!(percentile(&times[..1], 95), Duration::from_nanos(1));
+    }
+
+    #[test]
+    fn format_rows_works() {
+        let columns = [Column::text("name", 6), Column::number("size", 5)];
+        let rows = vec![
+            vec!["quick".to_string(), "10".to_string()],
+            vec!["merge".to_string(), "200".to_string()],
+        ];
+        assert_eq!(
+            format_rows(&columns, &rows, Format::Table),
+            "name    size\nquick     10\nmerge    200\n"
+        );
+        assert_eq!(
+            format_rows(&columns, &rows, Format::Csv),
+            "name,size\nquick,10\nmerge,200\n"
+        );
+        assert_eq!(
+            format_rows(&columns, &rows, Format::Json),
+            "[\n  {\"name\": \"quick\", \"size\": 10},\n  {\"name\": \"merge\", \"size\": 200}\n]\n"
+        );
+    }
+
+    #[test]
+    fn parse_args_works() {
+        let args = |line: &str| -> Vec<String> { line.split(' ').map(String::from).collect() };
+        let mut sizes = Vec::new();
+        let mut parse_flag = |flag: &str, value: &str| match flag {
+            "--sizes" => {
+                sizes = parse_list(value, |v| parse_number_at_least(flag, v, 2usize))?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        };
+
+        assert_eq!(
+            parse_args(&args("--sizes 2,30 --format csv"), "usage", &mut parse_flag),
+            Ok(Format::Csv)
+        );
+        assert_eq!(
+            parse_args(&args("--sizes 1"), "usage", &mut parse_flag),
+            Err("value for --sizes must be at least 2: 1".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("--sizes x"), "usage", &mut parse_flag),
+            Err("invalid value for --sizes: x".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("--format xml"), "usage", &mut parse_flag),
+            Err("unknown format: xml".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("--degree 3"), "usage", &mut parse_flag),
+            Err("unknown argument: --degree".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("--sizes"), "usage", &mut parse_flag),
+            Err("missing value for --sizes".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("--help"), "usage", &mut parse_flag),
+            Err("usage".to_string())
+        );
+        assert_eq!(sizes, [2, 30]);
+    }
+}
@@ -0,0 +1,62 @@
+//! Benchmark of graph searches (BFS, Dijkstra) on adjacency lists (Graph) and CSR layout (CsrGraph).
+//!
+//! Usage:
+//!   graphbench [--sizes 10000,100000] [--degree N] [--algorithms bfs,dijkstra] [--layouts adjacency,csr]
+//!              [--warmups N] [--runs N] [--seed N] [--format table|csv|json]
+use fun_with_algs::bench::{self, parse_list, parse_number, parse_number_at_least, Format};
+use fun_with_algs::search::bench::{format_results, run, Algorithm, GraphBenchConfig, Layout};
+use std::{env, process::ExitCode};
+
+fn usage() -> String {
+    let algorithms: Vec<&str> = Algorithm::ALL.iter().map(|a| a.name()).collect();
+    let layouts: Vec<&str> = Layout::ALL.iter().map(|l| l.name()).collect();
+    format!(
+        "usage: graphbench [--sizes N,..] [--degree N] [--algorithms A,..] [--layouts L,..] \
+         [--warmups N] [--runs N] [--seed N] [--format table|csv|json]\n\
+         algorithms: {}\n\
+         layouts: {}",
+        algorithms.join(","),
+        layouts.join(",")
+    )
+}
+
+fn parse_args(args: &[String]) -> Result<(GraphBenchConfig, Format), String> {
+    let mut config = GraphBenchConfig::default();
+    let format = bench::parse_args(args, &usage(), |flag, value| {
+        match flag {
+            // graph needs the start node and a not reachable target
+            "--sizes" => config.sizes = parse_list(value, |v| parse_number_at_least(flag, v, 2))?,
+            "--degree" => config.degree = parse_number(flag, value)?,
+            "--algorithms" => {
+                config.algorithms = parse_list(value, |v| {
+                    Algorithm::from_name(v).ok_or_else(|| format!("unknown algorithm: {v}"))
+                })?
+            }
+            "--layouts" => {
+                config.layouts = parse_list(value, |v| {
+                    Layout::from_name(v).ok_or_else(|| format!("unknown layout: {v}"))
+                })?
+            }
+            "--warmups" => config.warmups = parse_number(flag, value)?,
+            "--runs" => config.runs = parse_number_at_least(flag, value, 1)?,
+            "--seed" => config.seed = parse_number(flag, value)?,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+    Ok((config, format))
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (config, format) = match parse_args(&args) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("{message}");
+            return ExitCode::from(2);
+        }
+    };
+
+    print!("{}", format_results(&run(&config), format));
+    ExitCode::SUCCESS
+}
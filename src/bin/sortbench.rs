@@ -0,0 +1,66 @@
+//! Benchmark of registered sorters.
+//!
+//! Usage:
+//!   sortbench [--sizes 100,1000] [--distributions random,sorted,...] [--sorters quick,merge,...]
+//!             [--warmups N] [--runs N] [--seed N] [--format table|csv|json]
+use fun_with_algs::bench::{self, parse_list, parse_number, parse_number_at_least, Format};
+use fun_with_algs::sort::bench::{
+    format_results, registered_sorters, run, BenchConfig, Distribution,
+};
+use std::{env, process::ExitCode};
+
+fn usage() -> String {
+    let sorters: Vec<&str> = registered_sorters().iter().map(|s| s.name).collect();
+    let distributions: Vec<&str> = Distribution::ALL.iter().map(|d| d.name()).collect();
+    format!(
+        "usage: sortbench [--sizes N,..] [--distributions D,..] [--sorters S,..] \
+         [--warmups N] [--runs N] [--seed N] [--format table|csv|json]\n\
+         distributions: {}\n\
+         sorters: {}",
+        distributions.join(","),
+        sorters.join(",")
+    )
+}
+
+fn parse_args(args: &[String]) -> Result<(BenchConfig, Format), String> {
+    let mut config = BenchConfig::default();
+    let format = bench::parse_args(args, &usage(), |flag, value| {
+        match flag {
+            "--sizes" => config.sizes = parse_list(value, |v| parse_number(flag, v))?,
+            "--distributions" => {
+                config.distributions = parse_list(value, |v| {
+                    Distribution::from_name(v).ok_or_else(|| format!("unknown distribution: {v}"))
+                })?
+            }
+            "--sorters" => config.sorters = value.split(',').map(String::from).collect(),
+            "--warmups" => config.warmups = parse_number(flag, value)?,
+            "--runs" => config.runs = parse_number_at_least(flag, value, 1)?,
+            "--seed" => config.seed = parse_number(flag, value)?,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+    Ok((config, format))
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (config, format) = match parse_args(&args) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("{message}");
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(&config) {
+        Ok(results) => {
+            print!("{}", format_results(&results, format));
+            ExitCode::SUCCESS
+        }
+        Err(unknown) => {
+            eprintln!("unknown sorter: {unknown}\n{}", usage());
+            ExitCode::from(2)
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use crate::data::graph::{Graph, GraphNodeIndex};
+use crate::data::visit::{GraphBase, Neighbors};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Unweighted indexed graph stored as rows of bits (bit 'to' of row 'from' is set if there is edge from -> to),
+/// for small dense graphs: edge test is O(1) and operations on neighbour sets (e.g. common neighbours)
+/// process 64 nodes at once. Memory is V^2 bits.
+/// - V is node value type
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BitsetGraph<V> {
+    nodes: Vec<V>,
+    /// Number of u64 words of every row.
+    words_per_row: usize,
+    /// Row of node 'from' is at words from * words_per_row..(from + 1) * words_per_row.
+    rows: Vec<u64>,
+}
+
+/// Indexes of set bits in words, in increasing order.
+fn ones(words: &[u64]) -> impl Iterator<Item = usize> + '_ {
+    words.iter().enumerate().flat_map(|(i, &word)| {
+        // every step clears the lowest set bit
+        std::iter::successors((word != 0).then_some(word), |&bits| {
+            let rest = bits & (bits - 1);
+            (rest != 0).then_some(rest)
+        })
+        .map(move |bits| i * WORD_BITS + bits.trailing_zeros() as usize)
+    })
+}
+
+impl<V> BitsetGraph<V> {
+    /// Graph with nodes and without edges.
+    pub fn new(nodes: Vec<V>) -> Self {
+        let words_per_row = nodes.len().div_ceil(WORD_BITS);
+        let rows = vec![0; nodes.len() * words_per_row];
+        BitsetGraph {
+            nodes,
+            words_per_row,
+            rows,
+        }
+    }
+
+    pub fn nodes(&self) -> &[V] {
+        &self.nodes
+    }
+
+    pub fn nodes_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn row(&self, node: GraphNodeIndex) -> &[u64] {
+        &self.rows[node * self.words_per_row..(node + 1) * self.words_per_row]
+    }
+
+    /// Word of row 'from' with bit of 'to', and the bit.
+    fn position(&self, from: GraphNodeIndex, to: GraphNodeIndex) -> (usize, u64) {
+        assert!(
+            from < self.nodes.len() && to < self.nodes.len(),
+            "edge {from} -> {to} refers to a node out of bounds"
+        );
+        (
+            from * self.words_per_row + to / WORD_BITS,
+            1 << (to % WORD_BITS),
+        )
+    }
+
+    /// Add edge from -> to, returns false if it was already there.
+    pub fn add_edge(&mut self, from: GraphNodeIndex, to: GraphNodeIndex) -> bool {
+        let (word, bit) = self.position(from, to);
+        let added = self.rows[word] & bit == 0;
+        self.rows[word] |= bit;
+        added
+    }
+
+    /// Remove edge from -> to, returns false if there was no such edge.
+    pub fn remove_edge(&mut self, from: GraphNodeIndex, to: GraphNodeIndex) -> bool {
+        let (word, bit) = self.position(from, to);
+        let removed = self.rows[word] & bit != 0;
+        self.rows[word] &= !bit;
+        removed
+    }
+
+    pub fn has_edge(&self, from: GraphNodeIndex, to: GraphNodeIndex) -> bool {
+        let (word, bit) = self.position(from, to);
+        self.rows[word] & bit != 0
+    }
+
+    /// Neighbours of node, in order of index.
+    pub fn neighbours(&self, node: GraphNodeIndex) -> impl Iterator<Item = GraphNodeIndex> + '_ {
+        ones(self.row(node))
+    }
+
+    /// Number of edges going out of node.
+    pub fn out_degree(&self, node: GraphNodeIndex) -> usize {
+        self.row(node)
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Nodes which are neighbours of both a and b, in order of index.
+    pub fn common_neighbours(&self, a: GraphNodeIndex, b: GraphNodeIndex) -> Vec<GraphNodeIndex> {
+        let common: Vec<u64> = self
+            .row(a)
+            .iter()
+            .zip(self.row(b))
+            .map(|(a, b)| a & b)
+            .collect();
+        ones(&common).collect()
+    }
+
+    /// Number of nodes which are neighbours of both a and b (without listing them).
+    pub fn common_neighbours_count(&self, a: GraphNodeIndex, b: GraphNodeIndex) -> usize {
+        self.row(a)
+            .iter()
+            .zip(self.row(b))
+            .map(|(a, b)| (a & b).count_ones() as usize)
+            .sum()
+    }
+}
+
+impl<V: Clone> BitsetGraph<V> {
+    /// The same graph as rows of bits, edges data is dropped (and parallel edges become one edge).
+    pub fn from_graph<E>(graph: &Graph<V, E>) -> Self {
+        let mut bitset = BitsetGraph::new(graph.nodes.clone());
+        for (from, neighbours) in graph.edges.iter().enumerate() {
+            for (to, _) in neighbours {
+                bitset.add_edge(from, *to);
+            }
+        }
+        bitset
+    }
+
+    /// The same graph in Graph layout, neighbours of every node are in order of index.
+    pub fn to_graph(&self) -> Graph<V, ()> {
+        Graph {
+            nodes: self.nodes.clone(),
+            edges: (0..self.nodes.len())
+                .map(|node| self.neighbours(node).map(|to| (to, ())).collect())
+                .collect(),
+        }
+    }
+}
+
+impl<V> GraphBase for BitsetGraph<V> {
+    type NodeId = GraphNodeIndex;
+    type Edge = ();
+    type Map<T> = Vec<Option<T>>;
+
+    fn visit_map<T>(&self) -> Self::Map<T> {
+        (0..self.nodes.len()).map(|_| None).collect()
+    }
+}
+
+impl<V> Neighbors for BitsetGraph<V> {
+    fn for_each_neighbour<F: FnMut(&GraphNodeIndex, &())>(&self, node: &GraphNodeIndex, mut f: F) {
+        for neighbour in self.neighbours(*node) {
+            f(&neighbour, &());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::graph::{
+        make_random_weighted_indexed_graph, make_test_unweighted_indexed_graph,
+    };
+    use crate::rng::XorShift64;
+    use crate::search::bfs::{bfs_shortest_path, bfs_shortest_path_in_indexed_graph};
+    use crate::traverse::dfs::dfs_graph_traversal;
+
+    #[test]
+    fn bitset_graph_works() {
+        // rows longer than one word
+        let mut graph = BitsetGraph::new((0..130).collect());
+        assert!(graph.add_edge(0, 129));
+        assert!(!graph.add_edge(0, 129));
+        graph.add_edge(0, 64);
+        graph.add_edge(0, 1);
+        graph.add_edge(5, 64);
+        graph.add_edge(5, 129);
+        graph.add_edge(129, 63);
+        assert!(graph.has_edge(0, 64));
+        assert!(!graph.has_edge(64, 0));
+        assert_eq!(graph.neighbours(0).collect::<Vec<_>>(), [1, 64, 129]);
+        assert_eq!(graph.out_degree(0), 3);
+        assert_eq!(graph.common_neighbours(0, 5), [64, 129]);
+        assert_eq!(graph.common_neighbours_count(0, 5), 2);
+
+        assert_eq!(
+            bfs_shortest_path(&graph, 5, 63).unwrap().nodes,
+            [5, 129, 63]
+        );
+        assert!(graph.remove_edge(5, 129));
+        assert!(!graph.remove_edge(5, 129));
+        assert_eq!(bfs_shortest_path(&graph, 5, 63), None);
+    }
+
+    #[test]
+    fn bitset_graph_converts_from_and_to_graph() {
+        let graph = make_test_unweighted_indexed_graph();
+        let bitset = BitsetGraph::from_graph(&graph);
+        let mut sorted = graph.edges.clone();
+        for neighbours in &mut sorted {
+            neighbours.sort();
+        }
+        let back = bitset.to_graph();
+        assert_eq!((back.nodes, back.edges), (graph.nodes.clone(), sorted));
+
+        for start in 0..8 {
+            let mut dfs = dfs_graph_traversal(&bitset, start);
+            dfs.sort();
+            assert_eq!(dfs, (0..8).collect::<Vec<_>>());
+            for target in 0..8 {
+                assert_eq!(
+                    bfs_shortest_path(&bitset, start, target).map(|path| path.cost),
+                    bfs_shortest_path_in_indexed_graph(&graph, start, target).map(|path| path.cost)
+                );
+            }
+        }
+
+        let mut rng = XorShift64::new(48);
+        for _ in 0..20 {
+            let n = 1 + rng.index(150);
+            let graph = make_random_weighted_indexed_graph(&mut rng, n, 3 * n, 1);
+            let bitset = BitsetGraph::from_graph(&graph);
+            let (start, target) = (rng.index(n), rng.index(n));
+            assert_eq!(
+                bfs_shortest_path(&bitset, start, target).map(|path| path.cost),
+                bfs_shortest_path_in_indexed_graph(&graph, start, target).map(|path| path.cost)
+            );
+        }
+    }
+}
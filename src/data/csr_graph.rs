@@ -0,0 +1,242 @@
+use crate::data::graph::{Graph, GraphNodeIndex};
+use crate::data::graph_builder::GraphBuildError;
+use crate::data::visit::{GraphBase, Neighbors};
+
+/// Indexed graph in compressed sparse row layout: edges of all nodes are stored in two flat vecs,
+/// edges of node i are at offsets[i]..offsets[i + 1], so the whole graph is in 3 allocations
+/// (instead of one per node, as in Graph) and neighbours are scanned sequentially in memory.
+/// Graph is immutable, it is built at once from Graph or list of edges.
+/// - V is node value type
+/// - E is edge type
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CsrGraph<V, E> {
+    nodes: Vec<V>,
+    /// Start of edges of every node in 'targets' and 'edges', with the end of all edges as the last offset.
+    offsets: Vec<usize>,
+    targets: Vec<GraphNodeIndex>,
+    edges: Vec<E>,
+}
+
+impl<V, E> CsrGraph<V, E> {
+    pub fn nodes(&self) -> &[V] {
+        &self.nodes
+    }
+
+    pub fn nodes_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edges_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Neighbours of node with edges leading to them.
+    pub fn neighbours(
+        &self,
+        node: GraphNodeIndex,
+    ) -> impl Iterator<Item = (GraphNodeIndex, &E)> + '_ {
+        let range = self.offsets[node]..self.offsets[node + 1];
+        self.targets[range.clone()]
+            .iter()
+            .copied()
+            .zip(&self.edges[range])
+    }
+}
+
+impl<V, E: Clone> CsrGraph<V, E> {
+    /// Graph from list of edges (from, to, edge), neighbours of every node are in order of the list.
+    /// Returns Err if some edge refers to a node out of bounds.
+    /// Edges are written straight to their place (two passes over the list), so apart from the list
+    /// only memory of the graph itself is allocated.
+    pub fn from_edges(
+        nodes: Vec<V>,
+        edges: &[(GraphNodeIndex, GraphNodeIndex, E)],
+    ) -> Result<Self, GraphBuildError> {
+        let nodes_count = nodes.len();
+        if let Some(&(from, to, _)) = edges
+            .iter()
+            .find(|(from, to, _)| *from >= nodes_count || *to >= nodes_count)
+        {
+            return Err(GraphBuildError::EdgeOutOfBounds {
+                from,
+                to,
+                nodes_count,
+            });
+        }
+
+        // counting sort of edges by source node (stable, so order of neighbours is kept)
+        let mut offsets = vec![0; nodes_count + 1];
+        for (from, _, _) in edges {
+            offsets[from + 1] += 1;
+        }
+        for i in 0..nodes_count {
+            offsets[i + 1] += offsets[i];
+        }
+        // 'targets' holds position of edge in the list first, then it is replaced by its target
+        let mut targets = vec![0; edges.len()];
+        let mut next = offsets[..nodes_count].to_vec();
+        for (i, (from, _, _)) in edges.iter().enumerate() {
+            targets[next[*from]] = i;
+            next[*from] += 1;
+        }
+        let sorted_edges = targets.iter().map(|&i| edges[i].2.clone()).collect();
+        for target in &mut targets {
+            *target = edges[*target].1;
+        }
+
+        Ok(CsrGraph {
+            nodes,
+            offsets,
+            targets,
+            edges: sorted_edges,
+        })
+    }
+}
+
+impl<V: Clone, E: Clone> CsrGraph<V, E> {
+    /// The same graph in CSR layout (neighbours of every node are in the same order).
+    pub fn from_graph(graph: &Graph<V, E>) -> Self {
+        let mut offsets = Vec::with_capacity(graph.nodes.len() + 1);
+        let edges_count = graph.edges.iter().map(Vec::len).sum();
+        let mut targets = Vec::with_capacity(edges_count);
+        let mut edges = Vec::with_capacity(edges_count);
+        offsets.push(0);
+        for neighbours in &graph.edges {
+            for (to, edge) in neighbours {
+                targets.push(*to);
+                edges.push(edge.clone());
+            }
+            offsets.push(targets.len());
+        }
+        CsrGraph {
+            nodes: graph.nodes.clone(),
+            offsets,
+            targets,
+            edges,
+        }
+    }
+
+    /// The same graph in Graph layout.
+    pub fn to_graph(&self) -> Graph<V, E> {
+        Graph {
+            nodes: self.nodes.clone(),
+            edges: (0..self.nodes.len())
+                .map(|node| {
+                    self.neighbours(node)
+                        .map(|(to, edge)| (to, edge.clone()))
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<V, E> GraphBase for CsrGraph<V, E> {
+    type NodeId = GraphNodeIndex;
+    type Edge = E;
+    type Map<T> = Vec<Option<T>>;
+
+    fn visit_map<T>(&self) -> Self::Map<T> {
+        (0..self.nodes.len()).map(|_| None).collect()
+    }
+}
+
+impl<V, E> Neighbors for CsrGraph<V, E> {
+    fn for_each_neighbour<F: FnMut(&GraphNodeIndex, &E)>(&self, node: &GraphNodeIndex, mut f: F) {
+        for (neighbour, edge) in self.neighbours(*node) {
+            f(&neighbour, edge);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::graph::{
+        make_random_weighted_indexed_graph, make_test_unweighted_indexed_graph,
+        make_test_weighted_indexed_graph,
+    };
+    use crate::rng::XorShift64;
+    use crate::search::{
+        bfs::{bfs_shortest_path, bfs_shortest_path_in_indexed_graph},
+        dfs::dfs_path,
+        dijkstra::{dijkstra_shortest_path, dijkstra_shortest_path_in_weighted_indexed_graph},
+    };
+    use crate::traverse::{bfs::bfs_graph_traversal, dfs::dfs_graph_traversal};
+
+    #[test]
+    fn csr_graph_is_built_from_graph_and_edges() {
+        let graph = make_test_weighted_indexed_graph();
+        let csr = CsrGraph::from_graph(&graph);
+        assert_eq!(csr.nodes_count(), 8);
+        assert_eq!(csr.edges_count(), 24);
+        assert_eq!(
+            csr.neighbours(5).collect::<Vec<_>>(),
+            [(6, &3), (4, &1), (7, &2)]
+        );
+        let back = csr.to_graph();
+        assert_eq!(
+            (back.nodes, back.edges),
+            (graph.nodes.clone(), graph.edges.clone())
+        );
+
+        // edges listed by target node, but neighbours keep the order of the list
+        let mut list: Vec<_> = graph
+            .edges
+            .iter()
+            .enumerate()
+            .flat_map(|(from, neighbours)| neighbours.iter().map(move |&(to, w)| (from, to, w)))
+            .collect();
+        list.sort_by_key(|&(_, to, _)| to);
+        let from_edges = CsrGraph::from_edges(graph.nodes.clone(), &list).unwrap();
+        for node in 0..8 {
+            let expected: Vec<_> = list
+                .iter()
+                .filter(|(from, _, _)| *from == node)
+                .map(|(_, to, w)| (*to, w))
+                .collect();
+            assert_eq!(from_edges.neighbours(node).collect::<Vec<_>>(), expected);
+        }
+
+        assert_eq!(
+            CsrGraph::from_edges(vec!['a'], &[(0, 2, ())]),
+            Err(GraphBuildError::EdgeOutOfBounds {
+                from: 0,
+                to: 2,
+                nodes_count: 1
+            })
+        );
+        let empty: CsrGraph<(), ()> = CsrGraph::from_edges(Vec::new(), &[]).unwrap();
+        assert_eq!(empty.edges_count(), 0);
+    }
+
+    #[test]
+    fn searches_work_on_csr_graph() {
+        let graph = make_test_unweighted_indexed_graph();
+        let csr = CsrGraph::from_graph(&graph);
+        for start in 0..8 {
+            assert_eq!(dfs_graph_traversal(&csr, start).len(), 8);
+            assert_eq!(bfs_graph_traversal(&csr, start).len(), 8);
+            for target in 0..8 {
+                assert_eq!(
+                    bfs_shortest_path(&csr, start, target),
+                    bfs_shortest_path_in_indexed_graph(&graph, start, target)
+                );
+                let path = dfs_path(&csr, start, target).unwrap();
+                assert_eq!((path[0], path[path.len() - 1]), (start, target));
+            }
+        }
+
+        let mut rng = XorShift64::new(47);
+        for _ in 0..20 {
+            let n = 1 + rng.index(30);
+            let graph = make_random_weighted_indexed_graph(&mut rng, n, 3 * n, 10);
+            let csr = CsrGraph::from_graph(&graph);
+            let (start, target) = (rng.index(n), rng.index(n));
+            assert_eq!(
+                dijkstra_shortest_path(&csr, start, target),
+                dijkstra_shortest_path_in_weighted_indexed_graph(&graph, start, target)
+            );
+        }
+    }
+}
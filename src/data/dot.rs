@@ -0,0 +1,355 @@
+use crate::data::graph::{Graph, GraphNodeIndex, LinkedGraph, LinkedNode};
+use crate::data::sync_graph::SyncLinkedGraph;
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+/// Makes label of node from its value or label of edge from its data.
+type Label<'a, T> = Box<dyn Fn(&T) -> String + 'a>;
+
+/// Graphviz DOT export of graph, e.g. for drawing found paths when debugging:
+///
+/// ```text
+/// let dot = Dot::new(&graph)
+///     .node_label(|value| value.to_string())
+///     .edge_label(|weight| weight.to_string())
+///     .highlight_path(&path.nodes)
+///     .undirected_if_symmetric();
+/// std::fs::write("graph.dot", dot.to_string())?; // dot -Tsvg graph.dot > graph.svg
+/// ```
+///
+/// Nodes are identified by GraphNodeIndex (for linked graphs: index in LinkedGraph::nodes()).
+pub struct Dot<'a, V, E: Clone> {
+    nodes: Vec<&'a V>,
+    /// Edges of indexed graph are borrowed, edges of linked graph are copied (they are behind RefCell/RwLock).
+    edges: Vec<Vec<(GraphNodeIndex, Cow<'a, E>)>>,
+    /// None means nodes are labeled by index.
+    node_label: Option<Label<'a, V>>,
+    /// None means edges have no labels.
+    edge_label: Option<Label<'a, E>>,
+    path: Vec<GraphNodeIndex>,
+    visit_order: Vec<GraphNodeIndex>,
+    undirected_if_symmetric: bool,
+}
+
+const HIGHLIGHT: &str = "color=red, penwidth=2";
+
+impl<'a, V, E: Clone> Dot<'a, V, E> {
+    fn with_nodes(nodes: Vec<&'a V>, edges: Vec<Vec<(GraphNodeIndex, Cow<'a, E>)>>) -> Self {
+        Dot {
+            nodes,
+            edges,
+            node_label: None,
+            edge_label: None,
+            path: Vec::new(),
+            visit_order: Vec::new(),
+            undirected_if_symmetric: false,
+        }
+    }
+
+    pub fn new(graph: &'a Graph<V, E>) -> Self {
+        Dot::with_nodes(
+            graph.nodes.iter().collect(),
+            graph
+                .edges
+                .iter()
+                .map(|neighbours| {
+                    neighbours
+                        .iter()
+                        .map(|(to, edge)| (*to, Cow::Borrowed(edge)))
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    /// Nodes are numbered in order of 'nodes', edges to nodes not in 'nodes' are skipped.
+    fn from_linked_nodes<N, F>(nodes: &'a [N], value: F) -> Self
+    where
+        N: LinkedNode<Edge = E>,
+        F: Fn(&'a N) -> &'a V,
+    {
+        let indices: HashMap<N::Key, GraphNodeIndex> = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (node.key(), index))
+            .collect();
+        let edges = nodes
+            .iter()
+            .map(|node| {
+                let mut neighbours = Vec::new();
+                node.for_each_neighbour(|neighbour, edge| {
+                    if let Some(&index) = indices.get(&neighbour.key()) {
+                        neighbours.push((index, Cow::Owned(edge.clone())));
+                    }
+                });
+                neighbours
+            })
+            .collect();
+        Dot::with_nodes(nodes.iter().map(value).collect(), edges)
+    }
+
+    /// Linked graph, nodes are identified by their index in LinkedGraph::nodes() (see LinkedGraph::index_of).
+    pub fn from_linked_graph(graph: &'a LinkedGraph<V, E>) -> Self {
+        Dot::from_linked_nodes(graph.nodes(), |node| &node.value)
+    }
+
+    /// Thread-safe linked graph, nodes are identified by their index in SyncLinkedGraph::nodes().
+    pub fn from_sync_linked_graph(graph: &'a SyncLinkedGraph<V, E>) -> Self {
+        Dot::from_linked_nodes(graph.nodes(), |node| &node.value)
+    }
+
+    /// Label of node made from its value (instead of its index).
+    pub fn node_label(mut self, label: impl Fn(&V) -> String + 'a) -> Self {
+        self.node_label = Some(Box::new(label));
+        self
+    }
+
+    /// Label of edge made from its data (e.g. weight).
+    pub fn edge_label(mut self, label: impl Fn(&E) -> String + 'a) -> Self {
+        self.edge_label = Some(Box::new(label));
+        self
+    }
+
+    /// Draw nodes of path and edges between consecutive nodes of path in red.
+    pub fn highlight_path(mut self, path: &[GraphNodeIndex]) -> Self {
+        self.path = path.to_vec();
+        self
+    }
+
+    /// Fill visited nodes with colour from blue (visited first) to red (visited last),
+    /// with the position in visit order (e.g. by BFS or DFS traversal) next to the node.
+    pub fn visit_order(mut self, order: &[GraphNodeIndex]) -> Self {
+        self.visit_order = order.to_vec();
+        self
+    }
+
+    /// Emit undirected graph if every edge u -> v has edge v -> u with the same label.
+    pub fn undirected_if_symmetric(mut self) -> Self {
+        self.undirected_if_symmetric = true;
+        self
+    }
+
+    /// All edges (from, to, label), in order of nodes.
+    fn labeled_edges(&self) -> Vec<(GraphNodeIndex, GraphNodeIndex, Option<String>)> {
+        self.edges
+            .iter()
+            .enumerate()
+            .flat_map(|(from, neighbours)| {
+                neighbours.iter().map(move |(to, edge)| {
+                    let label = self.edge_label.as_ref().map(|label| label(edge));
+                    (from, *to, label)
+                })
+            })
+            .collect()
+    }
+}
+
+/// Edges counted by (from, to, label).
+type EdgeCounts<'e> = HashMap<(GraphNodeIndex, GraphNodeIndex, &'e Option<String>), usize>;
+
+/// Every edge u -> v has as many reverse edges v -> u with the same label.
+fn is_symmetric(counts: &EdgeCounts) -> bool {
+    counts
+        .iter()
+        .all(|(&(from, to, label), count)| counts.get(&(to, from, label)) == Some(count))
+}
+
+/// Quoted DOT string.
+fn quote(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
+
+impl<V, E: Clone> fmt::Display for Dot<'_, V, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let edges = self.labeled_edges();
+        let mut counts: EdgeCounts = HashMap::new();
+        for (from, to, label) in &edges {
+            *counts.entry((*from, *to, label)).or_default() += 1;
+        }
+        let undirected = self.undirected_if_symmetric && is_symmetric(&counts);
+        let (kind, arrow) = if undirected {
+            ("graph", "--")
+        } else {
+            ("digraph", "->")
+        };
+
+        let on_path: HashSet<GraphNodeIndex> = self.path.iter().copied().collect();
+        let mut path_edges: HashSet<(GraphNodeIndex, GraphNodeIndex)> = HashSet::new();
+        for step in self.path.windows(2) {
+            path_edges.insert((step[0], step[1]));
+            if undirected {
+                path_edges.insert((step[1], step[0]));
+            }
+        }
+        let mut visited_at: HashMap<GraphNodeIndex, usize> = HashMap::new();
+        for (position, &node) in self.visit_order.iter().enumerate() {
+            visited_at.entry(node).or_insert(position);
+        }
+
+        writeln!(f, "{kind} {{")?;
+        for (index, value) in self.nodes.iter().enumerate() {
+            let label = match &self.node_label {
+                Some(label) => label(value),
+                None => index.to_string(),
+            };
+            let mut attributes = vec![format!("label={}", quote(&label))];
+            if let Some(&position) = visited_at.get(&index) {
+                // hue goes from blue (0.66) to red (0)
+                let last = self.visit_order.len().saturating_sub(1).max(1);
+                let hue = 0.66 * (1.0 - position as f64 / last as f64);
+                attributes.push(format!(
+                    "style=filled, fillcolor=\"{hue:.3} 0.400 1.000\", xlabel=\"{}\"",
+                    position + 1
+                ));
+            }
+            if on_path.contains(&index) {
+                attributes.push(HIGHLIGHT.to_string());
+            }
+            writeln!(f, "    {index} [{}];", attributes.join(", "))?;
+        }
+
+        // undirected edge u -- v is emitted once for every pair of edges u -> v, v -> u
+        let mut emitted: EdgeCounts = HashMap::new();
+        for (from, to, label) in &edges {
+            if undirected && from != to {
+                let count = emitted.entry((*to, *from, label)).or_default();
+                if *count > 0 {
+                    *count -= 1;
+                    continue; // reverse of already emitted edge
+                }
+                *emitted.entry((*from, *to, label)).or_default() += 1;
+            }
+            let mut attributes = Vec::new();
+            if let Some(label) = label {
+                attributes.push(format!("label={}", quote(label)));
+            }
+            if path_edges.contains(&(*from, *to)) {
+                attributes.push(HIGHLIGHT.to_string());
+            }
+            if attributes.is_empty() {
+                writeln!(f, "    {from} {arrow} {to};")?;
+            } else {
+                writeln!(f, "    {from} {arrow} {to} [{}];", attributes.join(", "))?;
+            }
+        }
+        writeln!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::graph::{
+        make_test_weighted_indexed_graph, make_test_weighted_linked_graph_nodes,
+    };
+    use crate::search::dijkstra::{
+        dijkstra_shortest_path_in_weighted_indexed_graph,
+        dijkstra_shortest_path_in_weighted_linked_graph,
+    };
+    use crate::traverse::bfs::bfs_graph_traversal_in_indexed_graph;
+
+    #[test]
+    fn dot_export_works() {
+        let graph = Graph {
+            nodes: vec!["start", "say \"hi\"", "end"],
+            edges: vec![vec![(1, 2u32), (2, 7)], vec![(2, 1)], vec![]],
+        };
+        assert_eq!(
+            Dot::new(&graph).to_string(),
+            "digraph {\n    0 [label=\"0\"];\n    1 [label=\"1\"];\n    2 [label=\"2\"];\n    \
+             0 -> 1;\n    0 -> 2;\n    1 -> 2;\n}\n"
+        );
+
+        let path = dijkstra_shortest_path_in_weighted_indexed_graph(&graph, 0, 2).unwrap();
+        let dot = Dot::new(&graph)
+            .node_label(|value| value.to_string())
+            .edge_label(|weight| weight.to_string())
+            .highlight_path(&path.nodes)
+            .undirected_if_symmetric()
+            .to_string();
+        assert_eq!(
+            dot,
+            "digraph {\n    \
+             0 [label=\"start\", color=red, penwidth=2];\n    \
+             1 [label=\"say \\\"hi\\\"\", color=red, penwidth=2];\n    \
+             2 [label=\"end\", color=red, penwidth=2];\n    \
+             0 -> 1 [label=\"2\", color=red, penwidth=2];\n    \
+             0 -> 2 [label=\"7\"];\n    \
+             1 -> 2 [label=\"1\", color=red, penwidth=2];\n}\n"
+        );
+    }
+
+    #[test]
+    fn dot_export_of_symmetric_graph_is_undirected() {
+        let graph = make_test_weighted_indexed_graph();
+        let order = bfs_graph_traversal_in_indexed_graph(&graph, 0);
+        let dot = Dot::new(&graph)
+            .edge_label(|weight| weight.to_string())
+            .visit_order(&order)
+            .highlight_path(&[1, 0, 4])
+            .undirected_if_symmetric()
+            .to_string();
+        assert!(dot.starts_with("graph {\n"));
+        // 24 directed edges are 12 undirected ones
+        assert_eq!(dot.matches(" -- ").count(), 12);
+        assert!(!dot.contains("->"));
+        assert!(dot.contains("    0 [label=\"0\", style=filled, fillcolor=\"0.660 0.400 1.000\", xlabel=\"1\", color=red, penwidth=2];"));
+        assert!(dot.contains("fillcolor=\"0.000 0.400 1.000\", xlabel=\"8\""));
+        // path 1 -> 0 is drawn on edge emitted as 0 -- 1
+        assert!(dot.contains("    0 -- 1 [label=\"1\", color=red, penwidth=2];"));
+        assert!(dot.contains("    3 -- 7 [label=\"2\"];"));
+
+        // labels differ in one direction, so graph is not symmetric
+        let mut graph = make_test_weighted_indexed_graph();
+        graph.edges[7][0].1 = 5;
+        let dot = Dot::new(&graph)
+            .edge_label(|weight| weight.to_string())
+            .undirected_if_symmetric()
+            .to_string();
+        assert!(dot.starts_with("digraph {\n"));
+        assert_eq!(dot.matches(" -> ").count(), 24);
+        // without labels it is symmetric
+        let dot = Dot::new(&graph).undirected_if_symmetric().to_string();
+        assert_eq!(dot.matches(" -- ").count(), 12);
+    }
+
+    #[test]
+    fn dot_export_of_linked_graph_works() {
+        let (graph, start, end) = make_test_weighted_linked_graph_nodes('b', Some('f'));
+        let path = dijkstra_shortest_path_in_weighted_linked_graph(start, end.unwrap()).unwrap();
+        let path: Vec<GraphNodeIndex> = path
+            .nodes
+            .iter()
+            .map(|node| graph.index_of(node).unwrap())
+            .collect();
+        let dot = Dot::from_linked_graph(&graph)
+            .node_label(|value| value.to_string())
+            .highlight_path(&path)
+            .undirected_if_symmetric()
+            .to_string();
+        assert!(dot.starts_with("graph {\n"));
+        assert_eq!(dot.matches(" -- ").count(), 12);
+        assert!(dot.contains("[label=\"f\", color=red, penwidth=2];"));
+        assert!(dot.contains("[label=\"c\"];"));
+
+        let mut sync_graph = SyncLinkedGraph::new();
+        let a = sync_graph.add_node('a');
+        let b = sync_graph.add_node('b');
+        sync_graph.add_edge(&a, &b, 3);
+        let dot = Dot::from_sync_linked_graph(&sync_graph)
+            .node_label(|value| value.to_string())
+            .edge_label(|weight| weight.to_string())
+            .to_string();
+        assert_eq!(
+            dot,
+            "digraph {\n    0 [label=\"a\"];\n    1 [label=\"b\"];\n    0 -> 1 [label=\"3\"];\n}\n"
+        );
+    }
+}
@@ -1,4 +1,7 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::{Ref, RefCell, RefMut},
+    rc::Rc,
+};
 
 /// Represents index of node in Graph.nodes vec.
 pub type GraphNodeIndex = usize;
@@ -17,28 +20,240 @@ pub struct Graph<V, E> {
     pub edges: Vec<Vec<(GraphNodeIndex, E)>>, // index of 'edges' is GraphNodeIndex, and represents
 }
 
-use std::hash::Hash;
+impl<V, E: Clone> Graph<V, E> {
+    /// Edges with reversed direction, i.e. for every edge a -> b there is edge b -> a.
+    /// Index of returned vec is GraphNodeIndex (the same as for 'edges').
+    pub fn reversed_edges(&self) -> Vec<Vec<(GraphNodeIndex, E)>> {
+        let mut reversed = vec![Vec::new(); self.nodes.len()];
+        for (from, neighbours) in self.edges.iter().enumerate() {
+            for (to, edge) in neighbours {
+                reversed[*to].push((from, edge.clone()));
+            }
+        }
+        reversed
+    }
+}
+
+use std::hash::{Hash, Hasher};
+
+/// Neighbours with edges of linked graph node.
+pub type LinkedNeighbours<V, E> = Vec<(Rc<GraphNode<V, E>>, E)>;
+/// Neighbours/edges of linked graph node.
+pub type GraphNodeNeighbours<V, E> = RefCell<LinkedNeighbours<V, E>>;
 
 /// Linked nodes graph representation.
+/// Nodes are compared and hashed by identity (address of the node, which is stable as long as node lives in Rc),
+/// not by value, so two nodes with the same value are different nodes.
 #[derive(Debug)]
 pub struct GraphNode<V, E> {
     pub value: V,
-    pub neighbours: RefCell<Vec<(Rc<GraphNode<V, E>>, E)>>,
+    pub neighbours: GraphNodeNeighbours<V, E>,
+}
+
+impl<V, E> GraphNode<V, E> {
+    /// New node without neighbours.
+    pub fn new(value: V) -> Rc<Self> {
+        Rc::new(GraphNode {
+            value,
+            neighbours: RefCell::new(Vec::new()),
+        })
+    }
+
+    /// Read access to neighbours.
+    pub fn read_neighbours(&self) -> Ref<'_, LinkedNeighbours<V, E>> {
+        self.neighbours.borrow()
+    }
+
+    /// Write access to neighbours.
+    pub fn write_neighbours(&self) -> RefMut<'_, LinkedNeighbours<V, E>> {
+        self.neighbours.borrow_mut()
+    }
 }
 
 impl<V, E> PartialEq for GraphNode<V, E> {
     fn eq(&self, other: &Self) -> bool {
-        std::ptr::eq(&self.value, &other.value)
+        std::ptr::eq(self, other)
     }
 }
 impl<V, E> Eq for GraphNode<V, E> {}
 impl<V, E> Hash for GraphNode<V, E> {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        let ptr = &self.value as *const V;
-        ptr.hash(state);
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::ptr::hash(self, state);
     }
 }
 
+/// Shared handle to node of linked graph, e.g. `Rc<GraphNode>` or `Arc<SyncGraphNode>`,
+/// so the same linked graph searches run for any pointer family.
+pub trait LinkedNode: Clone {
+    type Edge;
+    /// Identity of node, which can be used as a key of HashMap/HashSet.
+    type Key: Hash + Eq;
+
+    fn key(&self) -> Self::Key;
+    /// Call 'f' for every neighbour with the edge leading to it.
+    /// Neighbours are locked/borrowed during the call, so 'f' must not add edges to this node.
+    fn for_each_neighbour<F: FnMut(&Self, &Self::Edge)>(&self, f: F);
+}
+
+/// Node handle type (compared by identity) and owner type of linked graph nodes behind pointer
+/// (`Rc<GraphNode>` or `Arc<SyncGraphNode>`), so both pointer families have the same API.
+/// Node type must have `new(value) -> Pointer<Node>`, `read_neighbours()` and `write_neighbours()`.
+macro_rules! linked_graph_types {
+    (
+        $(#[$ref_meta:meta])* $node_ref:ident($ptr:ident<$node:ident>), $as_ptr:ident, $into_ptr:ident;
+        $(#[$owner_meta:meta])* $owner:ident;
+    ) => {
+        $(#[$ref_meta])*
+        pub struct $node_ref<V, E>($ptr<$node<V, E>>);
+
+        impl<V, E> $node_ref<V, E> {
+            pub fn new(node: $ptr<$node<V, E>>) -> Self {
+                $node_ref(node)
+            }
+
+            pub fn $as_ptr(&self) -> &$ptr<$node<V, E>> {
+                &self.0
+            }
+
+            pub fn $into_ptr(self) -> $ptr<$node<V, E>> {
+                self.0
+            }
+        }
+
+        impl<V, E> From<$ptr<$node<V, E>>> for $node_ref<V, E> {
+            fn from(node: $ptr<$node<V, E>>) -> Self {
+                $node_ref(node)
+            }
+        }
+
+        impl<V, E> Clone for $node_ref<V, E> {
+            fn clone(&self) -> Self {
+                $node_ref(self.0.clone())
+            }
+        }
+
+        impl<V, E> PartialEq for $node_ref<V, E> {
+            fn eq(&self, other: &Self) -> bool {
+                $ptr::ptr_eq(&self.0, &other.0)
+            }
+        }
+        impl<V, E> Eq for $node_ref<V, E> {}
+        impl<V, E> std::hash::Hash for $node_ref<V, E> {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                std::ptr::hash($ptr::as_ptr(&self.0), state);
+            }
+        }
+
+        impl<V, E> std::ops::Deref for $node_ref<V, E> {
+            type Target = $node<V, E>;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl<V: std::fmt::Debug, E> std::fmt::Debug for $node_ref<V, E> {
+            /// Only value of node is printed (neighbours can have cycles).
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_tuple(stringify!($node_ref))
+                    .field(&self.0.value)
+                    .finish()
+            }
+        }
+
+        impl<V, E> $crate::data::graph::LinkedNode for $ptr<$node<V, E>> {
+            type Edge = E;
+            type Key = $node_ref<V, E>;
+
+            fn key(&self) -> Self::Key {
+                $node_ref::new(self.clone())
+            }
+
+            fn for_each_neighbour<F: FnMut(&Self, &E)>(&self, mut f: F) {
+                for (neighbour, edge) in self.read_neighbours().iter() {
+                    f(neighbour, edge);
+                }
+            }
+        }
+
+        $(#[$owner_meta])*
+        pub struct $owner<V, E> {
+            nodes: Vec<$ptr<$node<V, E>>>,
+        }
+
+        impl<V, E> $owner<V, E> {
+            pub fn new() -> Self {
+                $owner { nodes: Vec::new() }
+            }
+
+            /// Add node without neighbours, returns handle to it.
+            pub fn add_node(&mut self, value: V) -> $ptr<$node<V, E>> {
+                let node = $node::new(value);
+                self.nodes.push(node.clone());
+                node
+            }
+
+            /// Add directed edge from -> to. Both nodes must belong to this graph
+            /// (edges of other nodes would not be removed on drop), which is checked in debug builds.
+            pub fn add_edge(&self, from: &$ptr<$node<V, E>>, to: &$ptr<$node<V, E>>, edge: E) {
+                debug_assert!(
+                    self.index_of(from).is_some() && self.index_of(to).is_some(),
+                    "edge refers to a node which does not belong to this graph"
+                );
+                from.write_neighbours().push((to.clone(), edge));
+            }
+
+            /// All nodes, in order of adding.
+            pub fn nodes(&self) -> &[$ptr<$node<V, E>>] {
+                &self.nodes
+            }
+
+            /// Position of node in nodes() (None if node does not belong to this graph).
+            pub fn index_of(
+                &self,
+                node: &$ptr<$node<V, E>>,
+            ) -> Option<$crate::data::graph::GraphNodeIndex> {
+                self.nodes.iter().position(|n| $ptr::ptr_eq(n, node))
+            }
+        }
+
+        impl<V, E: Clone> $owner<V, E> {
+            /// Add edges a -> b and b -> a.
+            pub fn add_undirected_edge(&self, a: &$ptr<$node<V, E>>, b: &$ptr<$node<V, E>>, edge: E) {
+                self.add_edge(a, b, edge.clone());
+                self.add_edge(b, a, edge);
+            }
+        }
+
+        impl<V, E> Default for $owner<V, E> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<V, E> Drop for $owner<V, E> {
+            fn drop(&mut self) {
+                // graph still holds all nodes, so none of them is dropped while its edges are cleared
+                for node in &self.nodes {
+                    node.write_neighbours().clear();
+                }
+            }
+        }
+    };
+}
+pub(crate) use linked_graph_types;
+
+linked_graph_types!(
+    /// Handle to linked graph node, compared and hashed by identity of the node (`Rc::ptr_eq`),
+    /// so it can be used as a key of HashMap/HashSet (node value and neighbours do not matter).
+    NodeRef(Rc<GraphNode>), as_rc, into_rc;
+    /// Owner of linked graph nodes, which does not leak them.
+    /// Nodes of undirected (or any cyclic) linked graph hold each other by Rc, so they would never be dropped;
+    /// LinkedGraph keeps all its nodes and removes all edges when it is dropped, which breaks the cycles.
+    /// Node handles (Rc) can outlive the graph, but then they have no neighbours.
+    LinkedGraph;
+);
+
 ///////////--- Test graph --- /////////
 //                 //                //
 //      g - b      //      6 - 1     //
@@ -127,43 +342,50 @@ pub fn make_test_weighted_indexed_graph() -> Graph<char, u64> {
     graph
 }
 
+/// Random directed graph with 'nodes_count' nodes (values are indexes) and 'edges_count' edges,
+/// with weights in range 1..=max_weight.
+#[cfg(test)]
+pub fn make_random_weighted_indexed_graph(
+    rng: &mut crate::rng::XorShift64,
+    nodes_count: usize,
+    edges_count: usize,
+    max_weight: u64,
+) -> Graph<usize, u64> {
+    let mut graph = Graph {
+        nodes: (0..nodes_count).collect(),
+        edges: vec![Vec::new(); nodes_count],
+    };
+    for _ in 0..edges_count {
+        let from = rng.index(nodes_count);
+        let to = rng.index(nodes_count);
+        graph.edges[from].push((to, 1 + rng.below(max_weight)));
+    }
+    graph
+}
+
+/// Test linked graph (owner of nodes, which has to be kept alive while nodes are used)
+/// with (start, optional end) nodes.
+#[cfg(test)]
+pub type TestLinkedGraphNodes<E> = (
+    LinkedGraph<char, E>,
+    Rc<GraphNode<char, E>>,
+    Option<Rc<GraphNode<char, E>>>,
+);
+
 #[cfg(test)]
 pub fn make_test_unweighted_linked_graph_nodes(
     start: char,
     end: Option<char>,
-) -> (Rc<GraphNode<char, ()>>, Option<Rc<GraphNode<char, ()>>>) {
-    let a = Rc::new(GraphNode {
-        value: 'a',
-        neighbours: RefCell::new(vec![]),
-    });
-    let b = Rc::new(GraphNode {
-        value: 'b',
-        neighbours: RefCell::new(vec![]),
-    });
-    let c = Rc::new(GraphNode {
-        value: 'c',
-        neighbours: RefCell::new(vec![]),
-    });
-    let d = Rc::new(GraphNode {
-        value: 'd',
-        neighbours: RefCell::new(vec![]),
-    });
-    let e = Rc::new(GraphNode {
-        value: 'e',
-        neighbours: RefCell::new(vec![]),
-    });
-    let f = Rc::new(GraphNode {
-        value: 'f',
-        neighbours: RefCell::new(vec![]),
-    });
-    let g = Rc::new(GraphNode {
-        value: 'g',
-        neighbours: RefCell::new(vec![]),
-    });
-    let h = Rc::new(GraphNode {
-        value: 'h',
-        neighbours: RefCell::new(vec![]),
-    });
+) -> TestLinkedGraphNodes<()> {
+    let mut graph = LinkedGraph::new();
+    let a = graph.add_node('a');
+    let b = graph.add_node('b');
+    let c = graph.add_node('c');
+    let d = graph.add_node('d');
+    let e = graph.add_node('e');
+    let f = graph.add_node('f');
+    let g = graph.add_node('g');
+    let h = graph.add_node('h');
 
     // 'a' neigbours
     (*a.neighbours.borrow_mut()).append(&mut vec![
@@ -239,46 +461,23 @@ pub fn make_test_unweighted_linked_graph_nodes(
         _ => None,
     };
 
-    (start, end)
+    (graph, start, end)
 }
 
 #[cfg(test)]
 pub fn make_test_weighted_linked_graph_nodes(
     start: char,
     end: Option<char>,
-) -> (Rc<GraphNode<char, u64>>, Option<Rc<GraphNode<char, u64>>>) {
-    let a = Rc::new(GraphNode {
-        value: 'a',
-        neighbours: RefCell::new(vec![]),
-    });
-    let b = Rc::new(GraphNode {
-        value: 'b',
-        neighbours: RefCell::new(vec![]),
-    });
-    let c = Rc::new(GraphNode {
-        value: 'c',
-        neighbours: RefCell::new(vec![]),
-    });
-    let d = Rc::new(GraphNode {
-        value: 'd',
-        neighbours: RefCell::new(vec![]),
-    });
-    let e = Rc::new(GraphNode {
-        value: 'e',
-        neighbours: RefCell::new(vec![]),
-    });
-    let f = Rc::new(GraphNode {
-        value: 'f',
-        neighbours: RefCell::new(vec![]),
-    });
-    let g = Rc::new(GraphNode {
-        value: 'g',
-        neighbours: RefCell::new(vec![]),
-    });
-    let h = Rc::new(GraphNode {
-        value: 'h',
-        neighbours: RefCell::new(vec![]),
-    });
+) -> TestLinkedGraphNodes<u64> {
+    let mut graph = LinkedGraph::new();
+    let a = graph.add_node('a');
+    let b = graph.add_node('b');
+    let c = graph.add_node('c');
+    let d = graph.add_node('d');
+    let e = graph.add_node('e');
+    let f = graph.add_node('f');
+    let g = graph.add_node('g');
+    let h = graph.add_node('h');
 
     // 'a' neigbours
     (*a.neighbours.borrow_mut()).append(&mut vec![
@@ -338,5 +537,103 @@ pub fn make_test_weighted_linked_graph_nodes(
         _ => None,
     };
 
-    (start, end)
+    (graph, start, end)
+}
+
+#[test]
+fn linked_graph_node_identity_works() {
+    use std::collections::HashSet;
+
+    // the same values, also zero-sized, are different nodes
+    let a: Rc<GraphNode<(), ()>> = GraphNode::new(());
+    let b: Rc<GraphNode<(), ()>> = GraphNode::new(());
+    assert_eq!(a, a.clone());
+    assert_ne!(a, b);
+    assert_ne!(NodeRef::new(a.clone()), NodeRef::new(b.clone()));
+
+    let mut set = HashSet::new();
+    set.insert(NodeRef::new(a.clone()));
+    set.insert(NodeRef::new(b.clone()));
+    set.insert(NodeRef::from(a.clone()));
+    assert_eq!(set.len(), 2);
+
+    // identity does not change when neighbours change
+    a.neighbours.borrow_mut().push((b.clone(), ()));
+    assert!(set.contains(&NodeRef::new(a.clone())));
+    assert!(Rc::ptr_eq(NodeRef::new(a.clone()).as_rc(), &a));
+}
+
+#[test]
+fn linked_graph_does_not_leak() {
+    use std::cell::Cell;
+
+    /// Node value which counts its drops.
+    struct DropCounter(Rc<Cell<usize>>);
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let drops = Rc::new(Cell::new(0));
+
+    // cycle of plain nodes leaks
+    {
+        let a = GraphNode::new(DropCounter(drops.clone()));
+        let b = GraphNode::new(DropCounter(drops.clone()));
+        a.neighbours.borrow_mut().push((b.clone(), ()));
+        b.neighbours.borrow_mut().push((a.clone(), ()));
+        let weak = Rc::downgrade(&a);
+        drop((a, b));
+        assert_eq!(drops.get(), 0);
+        // clean up, so the test itself does not leak
+        weak.upgrade().unwrap().neighbours.borrow_mut().clear();
+    }
+    assert_eq!(drops.get(), 2);
+    drops.set(0);
+
+    // the same (and bigger) cycles in LinkedGraph are dropped together with the graph
+    {
+        let mut graph = LinkedGraph::new();
+        let nodes: Vec<_> = (0..5)
+            .map(|_| graph.add_node(DropCounter(drops.clone())))
+            .collect();
+        for i in 0..5 {
+            graph.add_undirected_edge(&nodes[i], &nodes[(i + 1) % 5], ());
+        }
+        graph.add_edge(&nodes[0], &nodes[0], ());
+        assert_eq!(graph.nodes().len(), 5);
+    }
+    assert_eq!(drops.get(), 5);
+    drops.set(0);
+
+    // handle can outlive the graph, then it is dropped with the last handle
+    let kept = {
+        let mut graph = LinkedGraph::new();
+        let a = graph.add_node(DropCounter(drops.clone()));
+        let b = graph.add_node(DropCounter(drops.clone()));
+        graph.add_undirected_edge(&a, &b, ());
+        a
+    };
+    assert_eq!(drops.get(), 1);
+    assert!(kept.neighbours.borrow().is_empty());
+    drop(kept);
+    assert_eq!(drops.get(), 2);
+
+    // test fixtures do not leak
+    let (graph, start, _) = make_test_weighted_linked_graph_nodes('a', None);
+    let weak = Rc::downgrade(&start);
+    drop((graph, start));
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "edge refers to a node which does not belong to this graph")]
+fn linked_graph_edge_to_foreign_node_panics() {
+    let mut graph: LinkedGraph<char, ()> = LinkedGraph::new();
+    let a = graph.add_node('a');
+    let mut other = LinkedGraph::new();
+    let b = other.add_node('b');
+    graph.add_edge(&a, &b, ());
 }
@@ -0,0 +1,228 @@
+use crate::data::graph::{Graph, GraphNodeIndex};
+use std::{collections::HashMap, error::Error, fmt};
+
+/// Error of building a graph by GraphBuilder.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GraphBuildError {
+    /// Edge refers to a node which was not added.
+    EdgeOutOfBounds {
+        from: GraphNodeIndex,
+        to: GraphNodeIndex,
+        nodes_count: usize,
+    },
+    /// The same label was given to two nodes.
+    DuplicateLabel {
+        label: String,
+        first: GraphNodeIndex,
+        second: GraphNodeIndex,
+    },
+}
+
+impl fmt::Display for GraphBuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphBuildError::EdgeOutOfBounds {
+                from,
+                to,
+                nodes_count,
+            } => write!(
+                f,
+                "edge {from} -> {to} refers to a node out of bounds (graph has {nodes_count} nodes)"
+            ),
+            GraphBuildError::DuplicateLabel {
+                label,
+                first,
+                second,
+            } => write!(f, "label {label:?} is given to nodes {first} and {second}"),
+        }
+    }
+}
+
+impl Error for GraphBuildError {}
+
+/// Builder of indexed Graph, which validates edges when graph is built.
+/// Edges can be added before nodes they point to, e.g. when graph is built from a grid.
+pub struct GraphBuilder<V, E> {
+    nodes: Vec<V>,
+    /// Edges in order of adding: (from, to, edge).
+    edges: Vec<(GraphNodeIndex, GraphNodeIndex, E)>,
+    labels: HashMap<String, GraphNodeIndex>,
+    /// The first duplicated label, reported by build().
+    duplicate_label: Option<GraphBuildError>,
+}
+
+impl<V, E> GraphBuilder<V, E> {
+    pub fn new() -> Self {
+        GraphBuilder {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            labels: HashMap::new(),
+            duplicate_label: None,
+        }
+    }
+
+    /// Add node, returns its index.
+    pub fn add_node(&mut self, value: V) -> GraphNodeIndex {
+        self.nodes.push(value);
+        self.nodes.len() - 1
+    }
+
+    /// Add node which can be found by label, returns its index.
+    pub fn add_labeled_node(&mut self, label: &str, value: V) -> GraphNodeIndex {
+        let index = self.add_node(value);
+        if let Some(&first) = self.labels.get(label) {
+            self.duplicate_label
+                .get_or_insert(GraphBuildError::DuplicateLabel {
+                    label: label.to_string(),
+                    first,
+                    second: index,
+                });
+        } else {
+            self.labels.insert(label.to_string(), index);
+        }
+        index
+    }
+
+    /// Add directed edge from -> to (indices are validated by build()).
+    pub fn add_edge(&mut self, from: GraphNodeIndex, to: GraphNodeIndex, edge: E) -> &mut Self {
+        self.edges.push((from, to, edge));
+        self
+    }
+
+    /// Index of node with label.
+    pub fn node_by_label(&self, label: &str) -> Option<GraphNodeIndex> {
+        self.labels.get(label).copied()
+    }
+
+    /// Index of the first node with value.
+    pub fn node_by_value(&self, value: &V) -> Option<GraphNodeIndex>
+    where
+        V: PartialEq,
+    {
+        self.nodes.iter().position(|node| node == value)
+    }
+
+    pub fn nodes_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Graph with all nodes and edges (neighbours of every node are in order of adding edges),
+    /// or error if some edge refers to a node out of bounds or some label is duplicated.
+    pub fn build(self) -> Result<Graph<V, E>, GraphBuildError> {
+        if let Some(error) = self.duplicate_label {
+            return Err(error);
+        }
+        let nodes_count = self.nodes.len();
+        let mut edges: Vec<Vec<(GraphNodeIndex, E)>> =
+            (0..nodes_count).map(|_| Vec::new()).collect();
+        for (from, to, edge) in self.edges {
+            if from >= nodes_count || to >= nodes_count {
+                return Err(GraphBuildError::EdgeOutOfBounds {
+                    from,
+                    to,
+                    nodes_count,
+                });
+            }
+            edges[from].push((to, edge));
+        }
+        Ok(Graph {
+            nodes: self.nodes,
+            edges,
+        })
+    }
+}
+
+impl<V, E: Clone> GraphBuilder<V, E> {
+    /// Add edges a -> b and b -> a.
+    pub fn add_undirected_edge(
+        &mut self,
+        a: GraphNodeIndex,
+        b: GraphNodeIndex,
+        edge: E,
+    ) -> &mut Self {
+        self.add_edge(a, b, edge.clone());
+        self.add_edge(b, a, edge)
+    }
+}
+
+impl<V, E> Default for GraphBuilder<V, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::graph::make_test_weighted_indexed_graph;
+    use crate::search::dijkstra::dijkstra_shortest_path_in_weighted_indexed_graph;
+
+    #[test]
+    fn graph_builder_works() {
+        let mut builder = GraphBuilder::new();
+        let home = builder.add_labeled_node("home", 'h');
+        let work = builder.add_labeled_node("work", 'w');
+        let shop = builder.add_node('s');
+        builder
+            .add_undirected_edge(home, work, 10u64)
+            .add_edge(home, shop, 3)
+            .add_edge(shop, work, 4);
+
+        assert_eq!(builder.node_by_label("work"), Some(work));
+        assert_eq!(builder.node_by_label("gym"), None);
+        assert_eq!(builder.node_by_value(&'s'), Some(shop));
+        assert_eq!(builder.nodes_count(), 3);
+
+        let graph = builder.build().unwrap();
+        assert_eq!(graph.nodes, ['h', 'w', 's']);
+        assert_eq!(
+            graph.edges,
+            [vec![(1, 10), (2, 3)], vec![(0, 10)], vec![(1, 4)]]
+        );
+        let path = dijkstra_shortest_path_in_weighted_indexed_graph(&graph, home, work).unwrap();
+        assert_eq!(path.nodes, [home, shop, work]);
+    }
+
+    #[test]
+    fn graph_builder_builds_test_graph() {
+        let expected = make_test_weighted_indexed_graph();
+        let mut builder = GraphBuilder::new();
+        for &value in &expected.nodes {
+            builder.add_node(value);
+        }
+        for (from, neighbours) in expected.edges.iter().enumerate() {
+            for &(to, weight) in neighbours {
+                builder.add_edge(from, to, weight);
+            }
+        }
+        let graph = builder.build().unwrap();
+        assert_eq!(graph.nodes, expected.nodes);
+        assert_eq!(graph.edges, expected.edges);
+    }
+
+    #[test]
+    fn graph_builder_reports_invalid_graph() {
+        let mut builder: GraphBuilder<char, ()> = GraphBuilder::new();
+        let a = builder.add_node('a');
+        builder.add_edge(a, 1, ());
+        let error = builder.build().err().unwrap();
+        assert_eq!(
+            error,
+            GraphBuildError::EdgeOutOfBounds {
+                from: 0,
+                to: 1,
+                nodes_count: 1
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "edge 0 -> 1 refers to a node out of bounds (graph has 1 nodes)"
+        );
+
+        let mut builder: GraphBuilder<char, ()> = GraphBuilder::new();
+        builder.add_labeled_node("x", 'a');
+        builder.add_labeled_node("x", 'b');
+        let error = builder.build().err().unwrap();
+        assert_eq!(error.to_string(), "label \"x\" is given to nodes 0 and 1");
+    }
+}
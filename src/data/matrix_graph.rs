@@ -0,0 +1,190 @@
+use crate::data::graph::{Graph, GraphNodeIndex};
+use crate::data::visit::{GraphBase, Neighbors};
+
+/// Indexed graph stored as adjacency matrix, for small dense graphs:
+/// edge test, adding and removing edge are O(1), but memory is O(V^2) and listing neighbours is O(V).
+/// There is at most one edge from -> to (no parallel edges).
+/// - V is node value type
+/// - E is edge type (e.g. weight)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MatrixGraph<V, E> {
+    nodes: Vec<V>,
+    /// Edge from -> to is at index from * nodes count + to, None if there is no edge.
+    matrix: Vec<Option<E>>,
+}
+
+impl<V, E> MatrixGraph<V, E> {
+    /// Graph with nodes and without edges.
+    pub fn new(nodes: Vec<V>) -> Self {
+        let matrix = (0..nodes.len() * nodes.len()).map(|_| None).collect();
+        MatrixGraph { nodes, matrix }
+    }
+
+    pub fn nodes(&self) -> &[V] {
+        &self.nodes
+    }
+
+    pub fn nodes_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn position(&self, from: GraphNodeIndex, to: GraphNodeIndex) -> usize {
+        assert!(
+            from < self.nodes.len() && to < self.nodes.len(),
+            "edge {from} -> {to} refers to a node out of bounds"
+        );
+        from * self.nodes.len() + to
+    }
+
+    /// Add edge from -> to, returns the replaced edge (if there was one).
+    pub fn add_edge(&mut self, from: GraphNodeIndex, to: GraphNodeIndex, edge: E) -> Option<E> {
+        let position = self.position(from, to);
+        self.matrix[position].replace(edge)
+    }
+
+    /// Remove edge from -> to, returns it (if there was one).
+    pub fn remove_edge(&mut self, from: GraphNodeIndex, to: GraphNodeIndex) -> Option<E> {
+        let position = self.position(from, to);
+        self.matrix[position].take()
+    }
+
+    pub fn edge(&self, from: GraphNodeIndex, to: GraphNodeIndex) -> Option<&E> {
+        self.matrix[self.position(from, to)].as_ref()
+    }
+
+    pub fn has_edge(&self, from: GraphNodeIndex, to: GraphNodeIndex) -> bool {
+        self.edge(from, to).is_some()
+    }
+
+    /// Neighbours of node with edges leading to them, in order of index.
+    pub fn neighbours(
+        &self,
+        node: GraphNodeIndex,
+    ) -> impl Iterator<Item = (GraphNodeIndex, &E)> + '_ {
+        let n = self.nodes.len();
+        self.matrix[node * n..(node + 1) * n]
+            .iter()
+            .enumerate()
+            .filter_map(|(to, edge)| Some((to, edge.as_ref()?)))
+    }
+}
+
+impl<V: Clone, E: Clone + Ord> MatrixGraph<V, E> {
+    /// The same graph as adjacency matrix. Of parallel edges only the cheapest (minimal) one is kept.
+    pub fn from_graph(graph: &Graph<V, E>) -> Self {
+        let mut matrix = MatrixGraph::new(graph.nodes.clone());
+        for (from, neighbours) in graph.edges.iter().enumerate() {
+            for (to, edge) in neighbours {
+                if matrix.edge(from, *to).is_none_or(|current| edge < current) {
+                    matrix.add_edge(from, *to, edge.clone());
+                }
+            }
+        }
+        matrix
+    }
+}
+
+impl<V: Clone, E: Clone> MatrixGraph<V, E> {
+    /// The same graph in Graph layout, neighbours of every node are in order of index.
+    pub fn to_graph(&self) -> Graph<V, E> {
+        Graph {
+            nodes: self.nodes.clone(),
+            edges: (0..self.nodes.len())
+                .map(|node| {
+                    self.neighbours(node)
+                        .map(|(to, edge)| (to, edge.clone()))
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<V, E> GraphBase for MatrixGraph<V, E> {
+    type NodeId = GraphNodeIndex;
+    type Edge = E;
+    type Map<T> = Vec<Option<T>>;
+
+    fn visit_map<T>(&self) -> Self::Map<T> {
+        (0..self.nodes.len()).map(|_| None).collect()
+    }
+}
+
+impl<V, E> Neighbors for MatrixGraph<V, E> {
+    fn for_each_neighbour<F: FnMut(&GraphNodeIndex, &E)>(&self, node: &GraphNodeIndex, mut f: F) {
+        for (neighbour, edge) in self.neighbours(*node) {
+            f(&neighbour, edge);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::graph::{
+        make_random_weighted_indexed_graph, make_test_weighted_indexed_graph,
+    };
+    use crate::rng::XorShift64;
+    use crate::search::{
+        bfs::{bfs_shortest_path, bfs_shortest_path_in_indexed_graph},
+        dijkstra::{dijkstra_shortest_path, dijkstra_shortest_path_in_weighted_indexed_graph},
+    };
+    use crate::traverse::dfs::dfs_graph_traversal;
+
+    #[test]
+    fn matrix_graph_works() {
+        let mut graph = MatrixGraph::new(vec!['a', 'b', 'c']);
+        assert_eq!(graph.add_edge(0, 1, 5u32), None);
+        assert_eq!(graph.add_edge(0, 1, 3), Some(5));
+        graph.add_edge(1, 2, 1);
+        graph.add_edge(0, 2, 7);
+        assert!(graph.has_edge(0, 1));
+        assert!(!graph.has_edge(1, 0));
+        assert_eq!(graph.edge(0, 1), Some(&3));
+        assert_eq!(graph.neighbours(0).collect::<Vec<_>>(), [(1, &3), (2, &7)]);
+
+        let path = dijkstra_shortest_path(&graph, 0, 2).unwrap();
+        assert_eq!((path.nodes, path.cost), (vec![0, 1, 2], 4));
+        assert_eq!(graph.remove_edge(1, 2), Some(1));
+        assert_eq!(dijkstra_shortest_path(&graph, 0, 2).unwrap().cost, 7);
+        assert_eq!(graph.remove_edge(1, 2), None);
+    }
+
+    #[test]
+    fn matrix_graph_converts_from_and_to_graph() {
+        let graph = make_test_weighted_indexed_graph();
+        let matrix = MatrixGraph::from_graph(&graph);
+        let mut sorted = graph.edges.clone();
+        for neighbours in &mut sorted {
+            neighbours.sort();
+        }
+        let back = matrix.to_graph();
+        assert_eq!((back.nodes, back.edges), (graph.nodes.clone(), sorted));
+
+        for start in 0..8 {
+            let mut dfs = dfs_graph_traversal(&matrix, start);
+            dfs.sort();
+            assert_eq!(dfs, (0..8).collect::<Vec<_>>());
+            for target in 0..8 {
+                assert_eq!(
+                    bfs_shortest_path(&matrix, start, target).map(|path| path.cost),
+                    bfs_shortest_path_in_indexed_graph(&graph, start, target).map(|path| path.cost)
+                );
+            }
+        }
+
+        // parallel edges: the cheapest one is kept, so costs of the cheapest paths are the same
+        let mut rng = XorShift64::new(48);
+        for _ in 0..20 {
+            let n = 1 + rng.index(15);
+            let graph = make_random_weighted_indexed_graph(&mut rng, n, 4 * n, 10);
+            let matrix = MatrixGraph::from_graph(&graph);
+            let (start, target) = (rng.index(n), rng.index(n));
+            assert_eq!(
+                dijkstra_shortest_path(&matrix, start, target).map(|path| path.cost),
+                dijkstra_shortest_path_in_weighted_indexed_graph(&graph, start, target)
+                    .map(|path| path.cost)
+            );
+        }
+    }
+}
@@ -1,2 +1,11 @@
 pub mod binary_tree;
+pub mod bitset_graph;
+pub mod csr_graph;
+pub mod dot;
 pub mod graph;
+pub mod graph_builder;
+pub mod matrix_graph;
+pub mod stable_graph;
+pub mod sync_graph;
+pub mod visit;
+pub mod weight;
@@ -0,0 +1,340 @@
+use crate::data::graph::{Graph, GraphNodeIndex};
+use crate::data::visit::{GraphBase, Neighbors};
+
+/// Indexed graph where nodes and edges can be removed without changing indices of other nodes.
+/// Removed node leaves a tombstone (empty slot), which is reused by the next added node (free list).
+/// Slots of removed nodes are skipped by searches; compact() removes them and renumbers nodes.
+/// - V is node value type
+/// - E is edge type
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StableGraph<V, E> {
+    /// Index of this vec is GraphNodeIndex, None for removed node.
+    nodes: Vec<Option<V>>,
+    /// Neighbours/edges of node, empty for removed node (edges of removed nodes are removed as well).
+    edges: Vec<Vec<(GraphNodeIndex, E)>>,
+    /// Slots of removed nodes, to be reused.
+    free: Vec<GraphNodeIndex>,
+}
+
+impl<V, E> StableGraph<V, E> {
+    pub fn new() -> Self {
+        StableGraph {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+
+    /// Add node, returns its index (slot of some removed node, if there is one).
+    pub fn add_node(&mut self, value: V) -> GraphNodeIndex {
+        if let Some(index) = self.free.pop() {
+            self.nodes[index] = Some(value);
+            return index;
+        }
+        self.nodes.push(Some(value));
+        self.edges.push(Vec::new());
+        self.nodes.len() - 1
+    }
+
+    /// Remove node with all its edges (incoming too), returns its value (None if there is no such node).
+    /// Indices of other nodes do not change.
+    pub fn remove_node(&mut self, node: GraphNodeIndex) -> Option<V> {
+        let value = self.nodes.get_mut(node)?.take()?;
+        self.edges[node].clear();
+        for neighbours in &mut self.edges {
+            neighbours.retain(|(to, _)| *to != node);
+        }
+        self.free.push(node);
+        Some(value)
+    }
+
+    pub fn contains_node(&self, node: GraphNodeIndex) -> bool {
+        self.nodes.get(node).is_some_and(Option::is_some)
+    }
+
+    pub fn node(&self, node: GraphNodeIndex) -> Option<&V> {
+        self.nodes.get(node)?.as_ref()
+    }
+
+    /// Number of (not removed) nodes.
+    pub fn nodes_count(&self) -> usize {
+        self.nodes.len() - self.free.len()
+    }
+
+    /// Indices of (not removed) nodes, in increasing order.
+    pub fn node_indices(&self) -> impl Iterator<Item = GraphNodeIndex> + '_ {
+        (0..self.nodes.len()).filter(|&node| self.contains_node(node))
+    }
+
+    fn assert_edge_nodes(&self, from: GraphNodeIndex, to: GraphNodeIndex) {
+        assert!(
+            self.contains_node(from) && self.contains_node(to),
+            "edge {from} -> {to} refers to a removed node or a node out of bounds"
+        );
+    }
+
+    /// Add edge from -> to (parallel edges are allowed). Panics if some of nodes does not exist.
+    pub fn add_edge(&mut self, from: GraphNodeIndex, to: GraphNodeIndex, edge: E) {
+        self.assert_edge_nodes(from, to);
+        self.edges[from].push((to, edge));
+    }
+
+    /// Remove (the first) edge from -> to, returns it (None if there is no such edge).
+    pub fn remove_edge(&mut self, from: GraphNodeIndex, to: GraphNodeIndex) -> Option<E> {
+        let neighbours = self.edges.get_mut(from)?;
+        let position = neighbours
+            .iter()
+            .position(|(neighbour, _)| *neighbour == to)?;
+        Some(neighbours.remove(position).1)
+    }
+
+    /// Replace (the first) edge from -> to, or add it if there is no such edge.
+    /// Returns the replaced edge. Panics if some of nodes does not exist.
+    pub fn update_edge(&mut self, from: GraphNodeIndex, to: GraphNodeIndex, edge: E) -> Option<E> {
+        self.assert_edge_nodes(from, to);
+        match self.edges[from]
+            .iter_mut()
+            .find(|(neighbour, _)| *neighbour == to)
+        {
+            Some((_, current)) => Some(std::mem::replace(current, edge)),
+            None => {
+                self.edges[from].push((to, edge));
+                None
+            }
+        }
+    }
+
+    /// Edge from -> to (the first one).
+    pub fn edge(&self, from: GraphNodeIndex, to: GraphNodeIndex) -> Option<&E> {
+        self.edges
+            .get(from)?
+            .iter()
+            .find(|(neighbour, _)| *neighbour == to)
+            .map(|(_, edge)| edge)
+    }
+
+    /// Neighbours of node with edges leading to them (none for removed node).
+    pub fn neighbours(&self, node: GraphNodeIndex) -> &[(GraphNodeIndex, E)] {
+        self.edges.get(node).map_or(&[], Vec::as_slice)
+    }
+
+    /// Remove slots of removed nodes, so nodes are numbered 0..nodes_count (in the same order).
+    /// Returns remap table: index of it is the old GraphNodeIndex, value is the new one (None for removed nodes).
+    pub fn compact(&mut self) -> Vec<Option<GraphNodeIndex>> {
+        let mut remap = vec![None; self.nodes.len()];
+        let mut new_index = 0;
+        for (old_index, node) in self.nodes.iter().enumerate() {
+            if node.is_some() {
+                remap[old_index] = Some(new_index);
+                new_index += 1;
+            }
+        }
+
+        let nodes = std::mem::take(&mut self.nodes);
+        let edges = std::mem::take(&mut self.edges);
+        for (node, neighbours) in nodes.into_iter().zip(edges) {
+            if node.is_some() {
+                self.nodes.push(node);
+                self.edges.push(
+                    neighbours
+                        .into_iter()
+                        .map(|(to, edge)| (remap[to].expect("edges lead only to nodes"), edge))
+                        .collect(),
+                );
+            }
+        }
+        self.free.clear();
+        remap
+    }
+
+    /// Graph with (not removed) nodes, numbered like after compact().
+    pub fn into_graph(mut self) -> Graph<V, E> {
+        self.compact();
+        Graph {
+            nodes: self.nodes.into_iter().flatten().collect(),
+            edges: self.edges,
+        }
+    }
+}
+
+impl<V, E> Default for StableGraph<V, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V, E> From<Graph<V, E>> for StableGraph<V, E> {
+    fn from(graph: Graph<V, E>) -> Self {
+        StableGraph {
+            nodes: graph.nodes.into_iter().map(Some).collect(),
+            edges: graph.edges,
+            free: Vec::new(),
+        }
+    }
+}
+
+/// Maps have a slot for every node slot. Searches do not start at removed nodes
+/// and never reach them (they have no edges).
+impl<V, E> GraphBase for StableGraph<V, E> {
+    type NodeId = GraphNodeIndex;
+    type Edge = E;
+    type Map<T> = Vec<Option<T>>;
+
+    fn visit_map<T>(&self) -> Self::Map<T> {
+        (0..self.nodes.len()).map(|_| None).collect()
+    }
+
+    fn contains_node(&self, node: &GraphNodeIndex) -> bool {
+        StableGraph::contains_node(self, *node)
+    }
+}
+
+impl<V, E> Neighbors for StableGraph<V, E> {
+    fn for_each_neighbour<F: FnMut(&GraphNodeIndex, &E)>(&self, node: &GraphNodeIndex, mut f: F) {
+        for (neighbour, edge) in self.neighbours(*node) {
+            f(neighbour, edge);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::graph::make_test_weighted_indexed_graph;
+    use crate::search::{
+        bfs::bfs_shortest_path,
+        dfs::dfs_path,
+        dijkstra::{dijkstra_shortest_path, dijkstra_shortest_path_in_weighted_indexed_graph},
+    };
+    use crate::traverse::{bfs::bfs_graph_traversal, dfs::dfs_graph_traversal};
+
+    ///////////--- Test graph --- /////////
+    //                 //                //
+    //      g - b      //      6 - 1     //
+    //     /   / \     //     /   / \    //
+    //    /   a - c    //    /   0 - 2   //
+    //   /   / \ /     //   /   / \ /    //
+    //  f - e - d      //  5 - 4 - 3     //
+    //   \     /       //   \     /      //
+    //    \   /        //    \   /       //
+    //      h          //      7         //
+    //                 //                //
+    ///////////////////////////////////////
+    #[test]
+    fn searches_skip_removed_nodes() {
+        let mut graph = StableGraph::from(make_test_weighted_indexed_graph());
+        assert_eq!(
+            dijkstra_shortest_path(&graph, 0, 5).unwrap().nodes,
+            [0, 4, 5]
+        );
+
+        // without 'e' the way to 'f' goes through 'h' or 'g'
+        assert_eq!(graph.remove_node(4), Some('e'));
+        assert_eq!(graph.remove_node(4), None);
+        assert!(!graph.contains_node(4));
+        assert_eq!(graph.nodes_count(), 7);
+        assert_eq!(graph.edge(0, 4), None);
+        let path = dijkstra_shortest_path(&graph, 0, 5).unwrap();
+        assert_eq!(path.cost, 5);
+        assert!(!path.nodes.contains(&4));
+        assert_eq!(bfs_shortest_path(&graph, 0, 4), None);
+        assert_eq!(bfs_shortest_path(&graph, 4, 0), None);
+        // removed node is not even a path to itself
+        assert_eq!(bfs_shortest_path(&graph, 4, 4), None);
+        assert_eq!(dijkstra_shortest_path(&graph, 4, 4), None);
+        assert_eq!(dfs_path(&graph, 4, 4), None);
+        assert_eq!(bfs_graph_traversal(&graph, 4), []);
+        assert_eq!(dfs_graph_traversal(&graph, 4), []);
+        assert_eq!(dijkstra_shortest_path(&graph, 3, 3).unwrap().nodes, [3]);
+        // other indices are not changed
+        assert_eq!(graph.node(5), Some(&'f'));
+
+        // removed slot is reused
+        assert_eq!(graph.add_node('x'), 4);
+        assert_eq!(bfs_shortest_path(&graph, 0, 4), None);
+        graph.add_edge(0, 4, 1);
+        graph.add_edge(4, 5, 1);
+        assert_eq!(
+            dijkstra_shortest_path(&graph, 0, 5).unwrap().nodes,
+            [0, 4, 5]
+        );
+    }
+
+    #[test]
+    fn edges_are_updated_and_removed() {
+        let mut graph = StableGraph::new();
+        let a = graph.add_node('a');
+        let b = graph.add_node('b');
+        let c = graph.add_node('c');
+        graph.add_edge(a, b, 1u32);
+        graph.add_edge(b, c, 1);
+        graph.add_edge(a, c, 5);
+        assert_eq!(dijkstra_shortest_path(&graph, a, c).unwrap().cost, 2);
+
+        assert_eq!(graph.update_edge(b, c, 10), Some(1));
+        assert_eq!(dijkstra_shortest_path(&graph, a, c).unwrap().cost, 5);
+        assert_eq!(graph.update_edge(c, a, 1), None);
+        assert_eq!(graph.edge(c, a), Some(&1));
+
+        assert_eq!(graph.remove_edge(a, c), Some(5));
+        assert_eq!(graph.remove_edge(a, c), None);
+        assert_eq!(dijkstra_shortest_path(&graph, a, c).unwrap().cost, 11);
+        assert_eq!(graph.remove_edge(7, a), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "edge 0 -> 1 refers to a removed node")]
+    fn edge_to_removed_node_panics() {
+        let mut graph = StableGraph::new();
+        graph.add_node('a');
+        graph.add_node('b');
+        graph.remove_node(1);
+        graph.add_edge(0, 1, ());
+    }
+
+    #[test]
+    fn compact_returns_remap() {
+        let original = make_test_weighted_indexed_graph();
+        let mut graph = StableGraph::from(make_test_weighted_indexed_graph());
+        graph.remove_node(2);
+        graph.remove_node(6);
+        let before: Vec<_> = graph
+            .node_indices()
+            .flat_map(|u| graph.node_indices().map(move |v| (u, v)))
+            .map(|(u, v)| dijkstra_shortest_path(&graph, u, v).map(|path| path.cost))
+            .collect();
+
+        let remap = graph.compact();
+        assert_eq!(
+            remap,
+            [
+                Some(0),
+                Some(1),
+                None,
+                Some(2),
+                Some(3),
+                Some(4),
+                None,
+                Some(5)
+            ]
+        );
+        assert_eq!(graph.node_indices().collect::<Vec<_>>(), [0, 1, 2, 3, 4, 5]);
+        assert_eq!(graph.node(5), Some(&'h'));
+        let after: Vec<_> = graph
+            .node_indices()
+            .flat_map(|u| graph.node_indices().map(move |v| (u, v)))
+            .map(|(u, v)| dijkstra_shortest_path(&graph, u, v).map(|path| path.cost))
+            .collect();
+        assert_eq!(before, after);
+        // new nodes are added at the end
+        assert_eq!(graph.add_node('i'), 6);
+
+        // without removed nodes it is the same graph
+        let graph = StableGraph::from(make_test_weighted_indexed_graph()).into_graph();
+        assert_eq!(graph.nodes, original.nodes);
+        assert_eq!(
+            dijkstra_shortest_path_in_weighted_indexed_graph(&graph, 1, 7),
+            dijkstra_shortest_path_in_weighted_indexed_graph(&original, 1, 7)
+        );
+    }
+}
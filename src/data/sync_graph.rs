@@ -0,0 +1,193 @@
+use crate::data::graph::linked_graph_types;
+use std::{
+    fmt::{self, Debug},
+    hash::{Hash, Hasher},
+    sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+/// Neighbours with edges of thread-safe linked graph node.
+pub type SyncNeighbours<V, E> = Vec<(Arc<SyncGraphNode<V, E>>, E)>;
+/// Neighbours/edges of thread-safe linked graph node, behind lock.
+pub type SyncGraphNodeNeighbours<V, E> = RwLock<SyncNeighbours<V, E>>;
+
+/// Thread-safe linked nodes graph representation: like GraphNode, but with Arc and RwLock,
+/// so graph can be shared between threads (searches take read lock, adding edges takes write lock).
+/// Nodes are compared and hashed by identity (address of the node), not by value.
+pub struct SyncGraphNode<V, E> {
+    pub value: V,
+    pub neighbours: SyncGraphNodeNeighbours<V, E>,
+}
+
+impl<V, E> SyncGraphNode<V, E> {
+    /// New node without neighbours.
+    pub fn new(value: V) -> Arc<Self> {
+        Arc::new(SyncGraphNode {
+            value,
+            neighbours: RwLock::new(Vec::new()),
+        })
+    }
+
+    /// Read access to neighbours.
+    /// Lock poisoned by panicking writer is ignored, as edges are always left in valid state.
+    pub fn read_neighbours(&self) -> RwLockReadGuard<'_, SyncNeighbours<V, E>> {
+        self.neighbours
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Write access to neighbours.
+    pub fn write_neighbours(&self) -> RwLockWriteGuard<'_, SyncNeighbours<V, E>> {
+        self.neighbours
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl<V, E> PartialEq for SyncGraphNode<V, E> {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+impl<V, E> Eq for SyncGraphNode<V, E> {}
+impl<V, E> Hash for SyncGraphNode<V, E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::ptr::hash(self, state);
+    }
+}
+
+impl<V: Debug, E> Debug for SyncGraphNode<V, E> {
+    /// Only value of node is printed (neighbours can have cycles and are locked).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SyncGraphNode")
+            .field("value", &self.value)
+            .finish_non_exhaustive()
+    }
+}
+
+linked_graph_types!(
+    /// Handle to thread-safe linked graph node, compared and hashed by identity of the node (`Arc::ptr_eq`).
+    SyncNodeRef(Arc<SyncGraphNode>), as_arc, into_arc;
+    /// Owner of thread-safe linked graph nodes, which does not leak them (see LinkedGraph).
+    /// Edges can be added concurrently through shared reference.
+    SyncLinkedGraph;
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::{
+        bfs::bfs_shortest_path_in_linked_graph, dfs::dfs_path_in_unweighted_linked_graph,
+        dijkstra::dijkstra_shortest_path_in_weighted_linked_graph,
+    };
+    use std::thread;
+
+    /// Path follows edges of graph.
+    fn assert_valid_path<V, E>(path: &[Arc<SyncGraphNode<V, E>>]) {
+        for w in path.windows(2) {
+            assert!(w[0]
+                .read_neighbours()
+                .iter()
+                .any(|(n, _)| Arc::ptr_eq(n, &w[1])));
+        }
+    }
+
+    #[test]
+    fn searches_work_on_sync_linked_graph() {
+        // 0 -1-> 1 -1-> 2 -1-> 3, and 0 -5-> 3
+        let mut graph = SyncLinkedGraph::new();
+        let nodes: Vec<_> = (0..4).map(|i| graph.add_node(i)).collect();
+        for i in 0..3 {
+            graph.add_edge(&nodes[i], &nodes[i + 1], 1u64);
+        }
+        graph.add_edge(&nodes[0], &nodes[3], 5);
+
+        let bfs = bfs_shortest_path_in_linked_graph(nodes[0].clone(), nodes[3].clone()).unwrap();
+        assert_eq!(bfs.cost, 1);
+        assert_eq!(
+            bfs.nodes.iter().map(|n| n.value).collect::<Vec<_>>(),
+            [0, 3]
+        );
+
+        let dijkstra =
+            dijkstra_shortest_path_in_weighted_linked_graph(nodes[0].clone(), nodes[3].clone())
+                .unwrap();
+        assert_eq!(dijkstra.cost, 3);
+        assert_eq!(
+            dijkstra.nodes.iter().map(|n| n.value).collect::<Vec<_>>(),
+            [0, 1, 2, 3]
+        );
+
+        let dfs = dfs_path_in_unweighted_linked_graph(nodes[0].clone(), nodes[3].clone()).unwrap();
+        assert_valid_path(&dfs);
+
+        assert!(bfs_shortest_path_in_linked_graph(nodes[3].clone(), nodes[0].clone()).is_none());
+    }
+
+    #[test]
+    fn concurrent_searches_while_edges_are_added() {
+        // chain 0 -> 1 -> ... -> n - 1, while writer adds shortcuts i -> i + 2
+        const N: usize = 60;
+        let mut graph = SyncLinkedGraph::new();
+        let nodes: Vec<_> = (0..N).map(|i| graph.add_node(i)).collect();
+        for i in 0..N - 1 {
+            graph.add_undirected_edge(&nodes[i], &nodes[i + 1], 2u32);
+        }
+        let (start, end) = (nodes[0].clone(), nodes[N - 1].clone());
+
+        thread::scope(|scope| {
+            let graph = &graph;
+            let nodes = &nodes;
+            scope.spawn(move || {
+                for i in (0..N - 2).step_by(2) {
+                    graph.add_edge(&nodes[i], &nodes[i + 2], 3);
+                    thread::yield_now();
+                }
+            });
+
+            for _ in 0..3 {
+                let (start, end) = (start.clone(), end.clone());
+                scope.spawn(move || {
+                    let mut last_bfs_cost = N;
+                    for _ in 0..50 {
+                        // end is always reachable and paths only get shorter
+                        let bfs =
+                            bfs_shortest_path_in_linked_graph(start.clone(), end.clone()).unwrap();
+                        assert!(bfs.cost <= last_bfs_cost);
+                        last_bfs_cost = bfs.cost;
+                        assert_valid_path(&bfs.nodes);
+
+                        let dijkstra = dijkstra_shortest_path_in_weighted_linked_graph(
+                            start.clone(),
+                            end.clone(),
+                        )
+                        .unwrap();
+                        assert!(dijkstra.cost <= 2 * (N as u32 - 1));
+                        assert_valid_path(&dijkstra.nodes);
+
+                        let dfs = dfs_path_in_unweighted_linked_graph(start.clone(), end.clone())
+                            .unwrap();
+                        assert_valid_path(&dfs);
+                    }
+                });
+            }
+        });
+
+        // all shortcuts are added: 29 shortcuts of cost 3 and the last edge of cost 2
+        let bfs = bfs_shortest_path_in_linked_graph(start.clone(), end.clone()).unwrap();
+        assert_eq!(bfs.cost, (N - 2) / 2 + 1);
+        let dijkstra = dijkstra_shortest_path_in_weighted_linked_graph(start, end).unwrap();
+        assert_eq!(dijkstra.cost, 3 * (N as u32 - 2) / 2 + 2);
+    }
+
+    #[test]
+    fn sync_linked_graph_does_not_leak() {
+        let weak = {
+            let mut graph: SyncLinkedGraph<char, ()> = SyncLinkedGraph::new();
+            let a = graph.add_node('a');
+            let b = graph.add_node('b');
+            graph.add_undirected_edge(&a, &b, ());
+            Arc::downgrade(&a)
+        };
+        assert!(weak.upgrade().is_none());
+    }
+}
@@ -0,0 +1,310 @@
+use crate::data::graph::{Graph, GraphNode, GraphNodeIndex, LinkedGraph, LinkedNode};
+use crate::data::sync_graph::{SyncGraphNode, SyncLinkedGraph};
+use std::{collections::HashMap, hash::Hash, marker::PhantomData, rc::Rc, sync::Arc};
+
+/// Graph seen by search algorithms: type of node identifiers and edges,
+/// and map from nodes to data of the search (visited flags, distances, predecessors).
+/// - Graph<V, E> nodes are GraphNodeIndex and maps are vecs indexed by them
+/// - linked graph (LinkedGraph, or node handle Rc<GraphNode> itself) nodes are node handles
+///   and maps are keyed by node identity
+/// - ImplicitGraph nodes are any hashable values with neighbours given by a successor closure
+pub trait GraphBase {
+    type NodeId: Clone;
+    type Edge;
+    type Map<T>: VisitMap<Self::NodeId, T>;
+
+    /// Empty map for all nodes of graph.
+    fn visit_map<T>(&self) -> Self::Map<T>;
+
+    /// Node belongs to graph, e.g. it is not removed from StableGraph.
+    /// Searches starting at node which does not belong to graph find nothing.
+    fn contains_node(&self, _node: &Self::NodeId) -> bool {
+        true
+    }
+}
+
+/// Graph which can list neighbours of its nodes.
+pub trait Neighbors: GraphBase {
+    /// Call 'f' for every neighbour of node with the edge leading to it.
+    fn for_each_neighbour<F: FnMut(&Self::NodeId, &Self::Edge)>(&self, node: &Self::NodeId, f: F);
+}
+
+/// Data of search stored for nodes of graph.
+pub trait VisitMap<N, T> {
+    fn get(&self, node: &N) -> Option<&T>;
+    fn insert(&mut self, node: N, value: T);
+
+    fn contains(&self, node: &N) -> bool {
+        self.get(node).is_some()
+    }
+}
+
+/// Map of indexed graph: index of vec is GraphNodeIndex, None if node has no data.
+impl<T> VisitMap<GraphNodeIndex, T> for Vec<Option<T>> {
+    fn get(&self, node: &GraphNodeIndex) -> Option<&T> {
+        self[*node].as_ref()
+    }
+
+    fn insert(&mut self, node: GraphNodeIndex, value: T) {
+        self[node] = Some(value);
+    }
+}
+
+impl<N: Hash + Eq, T> VisitMap<N, T> for HashMap<N, T> {
+    fn get(&self, node: &N) -> Option<&T> {
+        HashMap::get(self, node)
+    }
+
+    fn insert(&mut self, node: N, value: T) {
+        HashMap::insert(self, node, value);
+    }
+}
+
+impl<V, E> GraphBase for Graph<V, E> {
+    type NodeId = GraphNodeIndex;
+    type Edge = E;
+    type Map<T> = Vec<Option<T>>;
+
+    fn visit_map<T>(&self) -> Self::Map<T> {
+        (0..self.nodes.len()).map(|_| None).collect()
+    }
+}
+
+impl<V, E> Neighbors for Graph<V, E> {
+    fn for_each_neighbour<F: FnMut(&GraphNodeIndex, &E)>(&self, node: &GraphNodeIndex, mut f: F) {
+        for (neighbour, edge) in &self.edges[*node] {
+            f(neighbour, edge);
+        }
+    }
+}
+
+/// Linked graph of nodes reachable by handles of any LinkedNode type N, for code generic over N.
+/// Handles carry their neighbours, so it has no data and can be created for any start node.
+/// `Rc<GraphNode>`, `Arc<SyncGraphNode>` and their owners implement the traits themselves.
+pub struct LinkedNodes<N>(PhantomData<fn() -> N>);
+
+impl<N> LinkedNodes<N> {
+    pub fn new() -> Self {
+        LinkedNodes(PhantomData)
+    }
+}
+
+impl<N> Default for LinkedNodes<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Map of linked graph, keyed by identity of node (LinkedNode::key).
+pub struct LinkedVisitMap<N: LinkedNode, T>(HashMap<N::Key, T>);
+
+impl<N: LinkedNode, T> VisitMap<N, T> for LinkedVisitMap<N, T> {
+    fn get(&self, node: &N) -> Option<&T> {
+        self.0.get(&node.key())
+    }
+
+    fn insert(&mut self, node: N, value: T) {
+        self.0.insert(node.key(), value);
+    }
+}
+
+impl<N: LinkedNode> GraphBase for LinkedNodes<N> {
+    type NodeId = N;
+    type Edge = N::Edge;
+    type Map<T> = LinkedVisitMap<N, T>;
+
+    fn visit_map<T>(&self) -> Self::Map<T> {
+        LinkedVisitMap(HashMap::new())
+    }
+}
+
+impl<N: LinkedNode> Neighbors for LinkedNodes<N> {
+    fn for_each_neighbour<F: FnMut(&N, &N::Edge)>(&self, node: &N, f: F) {
+        node.for_each_neighbour(f);
+    }
+}
+
+/// Linked graphs: owner of nodes, or node handle which is the graph of nodes reachable from it,
+/// e.g. `bfs_shortest_path(&start, start.clone(), target)`.
+macro_rules! impl_linked_graph {
+    ($($graph:ty => $node:ty),*) => {
+        $(
+            impl<V, E> GraphBase for $graph {
+                type NodeId = $node;
+                type Edge = E;
+                type Map<T> = LinkedVisitMap<$node, T>;
+
+                fn visit_map<T>(&self) -> Self::Map<T> {
+                    LinkedVisitMap(HashMap::new())
+                }
+            }
+
+            impl<V, E> Neighbors for $graph {
+                fn for_each_neighbour<F: FnMut(&$node, &E)>(&self, node: &$node, f: F) {
+                    LinkedNode::for_each_neighbour(node, f);
+                }
+            }
+        )*
+    };
+}
+impl_linked_graph!(
+    Rc<GraphNode<V, E>> => Rc<GraphNode<V, E>>,
+    LinkedGraph<V, E> => Rc<GraphNode<V, E>>,
+    Arc<SyncGraphNode<V, E>> => Arc<SyncGraphNode<V, E>>,
+    SyncLinkedGraph<V, E> => Arc<SyncGraphNode<V, E>>
+);
+
+/// Graph which is not stored, but defined by closure returning successors of node with edges leading to them,
+/// e.g. states of a puzzle with moves between them.
+/// Nodes are found lazily, so the graph can be infinite (as long as search reaches its target).
+pub struct ImplicitGraph<N, F> {
+    successors: F,
+    _node: PhantomData<fn(&N)>,
+}
+
+impl<N, F> ImplicitGraph<N, F> {
+    pub fn new(successors: F) -> Self {
+        ImplicitGraph {
+            successors,
+            _node: PhantomData,
+        }
+    }
+}
+
+impl<N, E, F, I> GraphBase for ImplicitGraph<N, F>
+where
+    N: Clone + Hash + Eq,
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = (N, E)>,
+{
+    type NodeId = N;
+    type Edge = E;
+    type Map<T> = HashMap<N, T>;
+
+    fn visit_map<T>(&self) -> Self::Map<T> {
+        HashMap::new()
+    }
+}
+
+impl<N, E, F, I> Neighbors for ImplicitGraph<N, F>
+where
+    N: Clone + Hash + Eq,
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = (N, E)>,
+{
+    fn for_each_neighbour<G: FnMut(&N, &E)>(&self, node: &N, mut f: G) {
+        for (neighbour, edge) in (self.successors)(node) {
+            f(&neighbour, &edge);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::graph::{
+        make_test_weighted_indexed_graph, make_test_weighted_linked_graph_nodes,
+    };
+    use crate::search::{
+        bfs::{bfs_shortest_path, bfs_shortest_path_in_indexed_graph},
+        dfs::dfs_path,
+        dijkstra::{dijkstra_shortest_path, dijkstra_shortest_path_by},
+    };
+    use crate::traverse::{bfs::bfs_graph_traversal, dfs::dfs_graph_traversal};
+
+    /// Infinite graph of numbers with moves n -> n + 1 (cost 1), n -> n - 1 (cost 1) and n -> 2n (cost 3).
+    fn numbers() -> ImplicitGraph<i64, impl Fn(&i64) -> [(i64, u32); 3]> {
+        ImplicitGraph::new(|&n: &i64| [(n + 1, 1), (n - 1, 1), (2 * n, 3)])
+    }
+
+    /// Path follows moves of numbers graph and its cost is the sum of their costs.
+    fn path_cost(path: &[i64]) -> u32 {
+        path.windows(2)
+            .map(|w| {
+                (numbers().successors)(&w[0])
+                    .iter()
+                    .find(|(n, _)| *n == w[1])
+                    .expect("path follows moves")
+                    .1
+            })
+            .sum()
+    }
+
+    #[test]
+    fn searches_work_on_implicit_graph() {
+        let graph = numbers();
+        // 1 -> 2 -> 4 -> 5 -> 10
+        let bfs = bfs_shortest_path(&graph, 1, 10).unwrap();
+        assert_eq!(bfs.cost, 4);
+        assert_eq!(bfs.nodes.len(), 5);
+        path_cost(&bfs.nodes); // panics if path does not follow moves
+
+        // 1 -> 2 -> 3 -> 4 -> 5 -> 10
+        let dijkstra = dijkstra_shortest_path(&graph, 1, 10).unwrap();
+        assert_eq!(dijkstra.cost, 7);
+        assert_eq!(path_cost(&dijkstra.nodes), 7);
+        // every move costs the same, so it is BFS
+        let unweighted = dijkstra_shortest_path_by(&graph, 1, 10, |_| 1u32).unwrap();
+        assert_eq!(unweighted.cost as usize, bfs.cost);
+
+        // finite graph: n -> n + 1, ..., 2n (up to 20)
+        let graph = ImplicitGraph::new(|&n: &u32| (n + 1..=(2 * n).min(20)).map(|m| (m, ())));
+        let path = dfs_path(&graph, 1, 20).unwrap();
+        assert_eq!((path[0], path[path.len() - 1]), (1, 20));
+        assert!(path.windows(2).all(|w| w[0] < w[1] && w[1] <= 2 * w[0]));
+        assert_eq!(dfs_path(&graph, 2, 1), None);
+        assert_eq!(dfs_graph_traversal(&graph, 1).len(), 20);
+        assert_eq!(bfs_graph_traversal(&graph, 1).len(), 20);
+    }
+
+    #[test]
+    fn searches_are_the_same_for_all_representations() {
+        let graph = make_test_weighted_indexed_graph();
+        assert_eq!(
+            bfs_shortest_path(&graph, 1, 5),
+            bfs_shortest_path_in_indexed_graph(&graph, 1, 5)
+        );
+
+        // the same graph given by successors of indexes
+        let implicit = ImplicitGraph::new(|&n: &GraphNodeIndex| graph.edges[n].clone());
+        for start in 0..8 {
+            for target in 0..8 {
+                assert_eq!(
+                    dijkstra_shortest_path(&implicit, start, target).map(|path| path.cost),
+                    dijkstra_shortest_path(&graph, start, target).map(|path| path.cost)
+                );
+            }
+        }
+
+        let (linked, start, end) = make_test_weighted_linked_graph_nodes('b', Some('f'));
+        let end = end.unwrap();
+        let path = dijkstra_shortest_path(&linked, start.clone(), end.clone()).unwrap();
+        assert_eq!(path.cost, 3);
+        assert_eq!(
+            path.nodes.iter().map(|node| node.value).collect::<Vec<_>>(),
+            ['b', 'a', 'e', 'f']
+        );
+        // node handle is the graph of nodes reachable from it
+        assert_eq!(
+            dijkstra_shortest_path(&start, start.clone(), end.clone()),
+            Some(path)
+        );
+        assert_eq!(
+            bfs_shortest_path(&linked, start.clone(), end.clone()).map(|path| path.cost),
+            bfs_shortest_path(&graph, 1, 5).map(|path| path.cost)
+        );
+        assert_eq!(dfs_graph_traversal(&start, start.clone()).len(), 8);
+
+        let mut sync = SyncLinkedGraph::new();
+        let a = sync.add_node('a');
+        let b = sync.add_node('b');
+        sync.add_undirected_edge(&a, &b, 2u32);
+        assert_eq!(
+            dijkstra_shortest_path(&sync, b.clone(), a.clone())
+                .unwrap()
+                .cost,
+            2
+        );
+        assert_eq!(bfs_graph_traversal(&a, a.clone()).len(), 2);
+    }
+}
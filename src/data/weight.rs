@@ -0,0 +1,93 @@
+use std::{cmp::Ordering, fmt::Debug};
+
+/// Edge weight (cost/distance) used by shortest path algorithms.
+/// Implemented for all integer types and for `OrderedFloat`,
+/// user types (e.g. a cost with currency) can implement it as well.
+pub trait Weight: Clone + Ord + Debug {
+    /// Weight of empty path.
+    fn zero() -> Self;
+    /// Sum of weights, None if it does not fit in the type (overflow).
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+    /// Difference of weights, None if it does not fit in the type (e.g. negative for unsigned types).
+    fn checked_sub(&self, other: &Self) -> Option<Self>;
+}
+
+macro_rules! impl_weight_for_integers {
+    ($($t:ty),*) => {
+        $(
+            impl Weight for $t {
+                fn zero() -> Self {
+                    0
+                }
+                fn checked_add(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_add(*self, *other)
+                }
+                fn checked_sub(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_sub(*self, *other)
+                }
+            }
+        )*
+    };
+}
+impl_weight_for_integers!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Float with total order (by `total_cmp`), so it can be used as a Weight.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OrderedFloat<F>(pub F);
+
+macro_rules! impl_weight_for_floats {
+    ($($t:ty),*) => {
+        $(
+            impl PartialEq for OrderedFloat<$t> {
+                fn eq(&self, other: &Self) -> bool {
+                    self.cmp(other) == Ordering::Equal
+                }
+            }
+            impl Eq for OrderedFloat<$t> {}
+            impl PartialOrd for OrderedFloat<$t> {
+                fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                    Some(self.cmp(other))
+                }
+            }
+            impl Ord for OrderedFloat<$t> {
+                fn cmp(&self, other: &Self) -> Ordering {
+                    self.0.total_cmp(&other.0)
+                }
+            }
+            impl Weight for OrderedFloat<$t> {
+                fn zero() -> Self {
+                    OrderedFloat(0.0)
+                }
+                /// None if sum is not finite (overflow to infinity or NaN).
+                fn checked_add(&self, other: &Self) -> Option<Self> {
+                    let sum = self.0 + other.0;
+                    sum.is_finite().then_some(OrderedFloat(sum))
+                }
+                /// None if difference is not finite.
+                fn checked_sub(&self, other: &Self) -> Option<Self> {
+                    let difference = self.0 - other.0;
+                    difference.is_finite().then_some(OrderedFloat(difference))
+                }
+            }
+        )*
+    };
+}
+impl_weight_for_floats!(f32, f64);
+
+#[test]
+fn weight_works() {
+    assert_eq!(Weight::checked_add(&250u8, &5), Some(255));
+    assert_eq!(Weight::checked_add(&250u8, &6), None);
+    assert_eq!(Weight::checked_add(&-5i32, &3), Some(-2));
+    assert_eq!(Weight::checked_sub(&3u8, &5), None);
+    assert_eq!(Weight::checked_sub(&3i8, &5), Some(-2));
+    assert_eq!(
+        OrderedFloat(1.5).checked_add(&OrderedFloat(2.0)),
+        Some(OrderedFloat(3.5))
+    );
+    assert_eq!(
+        OrderedFloat(f64::MAX).checked_add(&OrderedFloat(f64::MAX)),
+        None
+    );
+    assert!(OrderedFloat(-0.5f32) < OrderedFloat::zero());
+}
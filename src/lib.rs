@@ -1,4 +1,6 @@
+pub mod bench;
 pub mod data;
+pub mod rng;
 pub mod search;
 pub mod sort;
 pub mod traverse;
@@ -0,0 +1,53 @@
+/// Small deterministic pseudo random number generator (xorshift64*).
+/// Good enough for generating benchmark inputs and random test data, NOT for cryptography.
+#[derive(Clone, Debug)]
+pub struct XorShift64 {
+    state: u64,
+}
+
+impl XorShift64 {
+    pub fn new(seed: u64) -> Self {
+        // state must never be 0, otherwise generator returns only zeros
+        let state = match seed ^ 0x9E37_79B9_7F4A_7C15 {
+            0 => 0x9E37_79B9_7F4A_7C15,
+            state => state,
+        };
+        XorShift64 { state }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.state = x;
+        x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Random number in range 0..bound (bound must be > 0).
+    pub fn below(&mut self, bound: u64) -> u64 {
+        debug_assert!(bound > 0);
+        self.next_u64() % bound
+    }
+
+    /// Random index in range 0..len (len must be > 0).
+    pub fn index(&mut self, len: usize) -> usize {
+        self.below(len as u64) as usize
+    }
+
+    /// Random float in range [0, 1).
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[test]
+fn xorshift_is_deterministic() {
+    let mut a = XorShift64::new(42);
+    let mut b = XorShift64::new(42);
+    for _ in 0..100 {
+        assert_eq!(a.next_u64(), b.next_u64());
+    }
+    let mut c = XorShift64::new(0);
+    assert!((0..100).map(|_| c.below(10)).all(|x| x < 10));
+}
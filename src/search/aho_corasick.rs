@@ -0,0 +1,447 @@
+use std::collections::VecDeque;
+
+/// Which matches are reported.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum MatchKind {
+    /// All occurrences of all patterns (matches can overlap).
+    #[default]
+    Overlapping,
+    /// Non overlapping matches, scanning from left: at each position the longest pattern wins.
+    LeftmostLongest,
+}
+
+/// Single match of pattern in haystack: haystack[start..end] is pattern with id 'pattern_id'.
+/// Pattern id is the index of pattern in the collection passed to `build`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Match {
+    pub pattern_id: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+type StateId = usize;
+const ROOT: StateId = 0;
+
+/// State of automaton, i.e. node of patterns trie.
+struct State {
+    // sorted by byte
+    transitions: Vec<(u8, StateId)>,
+    // state of the longest proper suffix which is also in trie
+    fail: StateId,
+    // the nearest state reachable by fail links which has some patterns
+    dictionary: Option<StateId>,
+    // ids of patterns ending in this state
+    patterns: Vec<usize>,
+    depth: usize,
+}
+
+impl State {
+    fn new(depth: usize) -> Self {
+        State {
+            transitions: Vec::new(),
+            fail: ROOT,
+            dictionary: None,
+            patterns: Vec::new(),
+            depth,
+        }
+    }
+
+    fn next(&self, byte: u8) -> Option<StateId> {
+        self.transitions
+            .binary_search_by_key(&byte, |&(b, _)| b)
+            .ok()
+            .map(|i| self.transitions[i].1)
+    }
+}
+
+/// Builder of Aho–Corasick automaton.
+#[derive(Clone, Debug, Default)]
+pub struct AhoCorasickBuilder {
+    match_kind: MatchKind,
+    ascii_case_insensitive: bool,
+}
+
+impl AhoCorasickBuilder {
+    pub fn new() -> Self {
+        AhoCorasickBuilder::default()
+    }
+
+    pub fn match_kind(mut self, match_kind: MatchKind) -> Self {
+        self.match_kind = match_kind;
+        self
+    }
+
+    /// Match ASCII letters regardless of their case (other bytes are compared exactly).
+    pub fn ascii_case_insensitive(mut self, yes: bool) -> Self {
+        self.ascii_case_insensitive = yes;
+        self
+    }
+
+    /// Build automaton from patterns. Empty patterns never match.
+    pub fn build<I, P>(&self, patterns: I) -> AhoCorasick
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<[u8]>,
+    {
+        let mut states = vec![State::new(0)];
+        let mut pattern_lens = Vec::new();
+
+        // 1. trie of all patterns
+        for (pattern_id, pattern) in patterns.into_iter().enumerate() {
+            let pattern = pattern.as_ref();
+            pattern_lens.push(pattern.len());
+            if pattern.is_empty() {
+                continue;
+            }
+            let mut current = ROOT;
+            for &byte in pattern {
+                let byte = self.normalize(byte);
+                current = match states[current].next(byte) {
+                    Some(next) => next,
+                    None => {
+                        let next = states.len();
+                        states.push(State::new(states[current].depth + 1));
+                        let transitions = &mut states[current].transitions;
+                        let insertion_index = transitions.partition_point(|&(b, _)| b < byte);
+                        transitions.insert(insertion_index, (byte, next));
+                        next
+                    }
+                };
+            }
+            states[current].patterns.push(pattern_id);
+        }
+
+        // 2. fail and dictionary links, by traversing the trie with BFS (so shorter states are done first)
+        let mut fifo: VecDeque<StateId> =
+            states[ROOT].transitions.iter().map(|&(_, s)| s).collect();
+        while let Some(current) = fifo.pop_front() {
+            for i in 0..states[current].transitions.len() {
+                let (byte, child) = states[current].transitions[i];
+                let mut fail = states[current].fail;
+                while fail != ROOT && states[fail].next(byte).is_none() {
+                    fail = states[fail].fail;
+                }
+                let fail = states[fail]
+                    .next(byte)
+                    .filter(|&s| s != child)
+                    .unwrap_or(ROOT);
+                states[child].fail = fail;
+                states[child].dictionary = if states[fail].patterns.is_empty() {
+                    states[fail].dictionary
+                } else {
+                    Some(fail)
+                };
+                fifo.push_back(child);
+            }
+        }
+
+        AhoCorasick {
+            states,
+            pattern_lens,
+            match_kind: self.match_kind,
+            ascii_case_insensitive: self.ascii_case_insensitive,
+        }
+    }
+
+    fn normalize(&self, byte: u8) -> u8 {
+        if self.ascii_case_insensitive {
+            byte.to_ascii_lowercase()
+        } else {
+            byte
+        }
+    }
+}
+
+/// Aho–Corasick automaton for searching many patterns at once in O(n + number of matches).
+pub struct AhoCorasick {
+    states: Vec<State>,
+    pattern_lens: Vec<usize>,
+    match_kind: MatchKind,
+    ascii_case_insensitive: bool,
+}
+
+impl AhoCorasick {
+    /// Automaton with default options (overlapping, case sensitive matches).
+    pub fn new<I, P>(patterns: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<[u8]>,
+    {
+        AhoCorasickBuilder::new().build(patterns)
+    }
+
+    pub fn patterns_len(&self) -> usize {
+        self.pattern_lens.len()
+    }
+
+    /// All matches in haystack (works for both &str and &[u8]), ordered by position.
+    pub fn find_all<H: AsRef<[u8]> + ?Sized>(&self, haystack: &H) -> Vec<Match> {
+        let mut stream = self.stream();
+        let mut matches = stream.feed(haystack.as_ref());
+        matches.append(&mut stream.finish());
+        matches
+    }
+
+    /// Searcher for input which comes in chunks, matches across chunk boundaries are found too.
+    pub fn stream(&self) -> StreamSearcher<'_> {
+        StreamSearcher {
+            automaton: self,
+            state: ROOT,
+            position: 0,
+            pending: Vec::new(),
+            last_end: 0,
+        }
+    }
+
+    fn next_state(&self, mut current: StateId, byte: u8) -> StateId {
+        let byte = if self.ascii_case_insensitive {
+            byte.to_ascii_lowercase()
+        } else {
+            byte
+        };
+        loop {
+            if let Some(next) = self.states[current].next(byte) {
+                return next;
+            }
+            if current == ROOT {
+                return ROOT;
+            }
+            current = self.states[current].fail;
+        }
+    }
+}
+
+/// Incremental search over chunks of input.
+/// Positions of matches are counted from the beginning of the whole stream.
+pub struct StreamSearcher<'a> {
+    automaton: &'a AhoCorasick,
+    state: StateId,
+    // number of bytes fed so far
+    position: usize,
+    // leftmost-longest only: matches which can still lose with a match which is not found yet
+    pending: Vec<Match>,
+    // leftmost-longest only: end of the last reported match
+    last_end: usize,
+}
+
+impl StreamSearcher<'_> {
+    /// Feed next chunk, returns matches which are already known to be final.
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<Match> {
+        let automaton = self.automaton;
+        let mut output = Vec::new();
+        for &byte in chunk {
+            self.state = automaton.next_state(self.state, byte);
+            self.position += 1;
+
+            // all patterns which end here: own patterns of the state and the ones reachable by dictionary links
+            let state = &automaton.states[self.state];
+            let mut current = if state.patterns.is_empty() {
+                state.dictionary
+            } else {
+                Some(self.state)
+            };
+            while let Some(state) = current {
+                for &pattern_id in &automaton.states[state].patterns {
+                    let found = Match {
+                        pattern_id,
+                        start: self.position - automaton.pattern_lens[pattern_id],
+                        end: self.position,
+                    };
+                    match automaton.match_kind {
+                        MatchKind::Overlapping => output.push(found),
+                        MatchKind::LeftmostLongest => {
+                            if found.start >= self.last_end {
+                                self.pending.push(found);
+                            }
+                        }
+                    }
+                }
+                current = automaton.states[state].dictionary;
+            }
+
+            if automaton.match_kind == MatchKind::LeftmostLongest {
+                // any future match starts at or after this position
+                let earliest_start = self.position - automaton.states[self.state].depth;
+                self.resolve_pending(earliest_start, &mut output);
+            }
+        }
+        output
+    }
+
+    /// End of input, returns remaining matches.
+    pub fn finish(mut self) -> Vec<Match> {
+        let mut output = Vec::new();
+        self.resolve_pending(usize::MAX, &mut output);
+        output
+    }
+
+    /// Report pending matches which can not be beaten by any match starting at or after 'earliest_start'.
+    fn resolve_pending(&mut self, earliest_start: usize, output: &mut Vec<Match>) {
+        // the leftmost, then the longest, then the first found (i.e. the lowest pattern id)
+        while let Some(best) = self.pending.iter().copied().reduce(|best, m| {
+            if m.start < best.start || (m.start == best.start && m.end > best.end) {
+                m
+            } else {
+                best
+            }
+        }) {
+            if best.start >= earliest_start {
+                break;
+            }
+            output.push(best);
+            self.last_end = best.end;
+            self.pending.retain(|m| m.start >= best.end);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::XorShift64;
+
+    fn brute_force_overlapping(patterns: &[&str], haystack: &str) -> Vec<Match> {
+        let mut output = Vec::new();
+        for end in 1..=haystack.len() {
+            // longer patterns first, like the automaton reports them
+            let mut found: Vec<Match> = patterns
+                .iter()
+                .enumerate()
+                .filter(|(_, p)| {
+                    !p.is_empty() && haystack.as_bytes()[..end].ends_with(p.as_bytes())
+                })
+                .map(|(pattern_id, p)| Match {
+                    pattern_id,
+                    start: end - p.len(),
+                    end,
+                })
+                .collect();
+            found.sort_by_key(|m| (m.start, m.pattern_id));
+            output.extend(found);
+        }
+        output
+    }
+
+    fn brute_force_leftmost_longest(patterns: &[&str], haystack: &str) -> Vec<Match> {
+        let mut output = Vec::new();
+        let mut start = 0;
+        while start < haystack.len() {
+            let longest = patterns
+                .iter()
+                .enumerate()
+                .filter(|(_, p)| {
+                    !p.is_empty() && haystack.as_bytes()[start..].starts_with(p.as_bytes())
+                })
+                .max_by_key(|&(pattern_id, p)| (p.len(), usize::MAX - pattern_id));
+            match longest {
+                Some((pattern_id, p)) => {
+                    output.push(Match {
+                        pattern_id,
+                        start,
+                        end: start + p.len(),
+                    });
+                    start += p.len();
+                }
+                None => start += 1,
+            }
+        }
+        output
+    }
+
+    #[test]
+    fn overlapping_matches_work() {
+        let patterns = ["he", "she", "his", "hers"];
+        let automaton = AhoCorasick::new(patterns);
+        let matches: Vec<(usize, usize, usize)> = automaton
+            .find_all("ushers")
+            .iter()
+            .map(|m| (m.pattern_id, m.start, m.end))
+            .collect();
+        assert_eq!(matches, [(1, 1, 4), (0, 2, 4), (3, 2, 6)]);
+    }
+
+    #[test]
+    fn leftmost_longest_matches_work() {
+        let patterns = ["ab", "c", "abcde", "abcd"];
+        let automaton = AhoCorasickBuilder::new()
+            .match_kind(MatchKind::LeftmostLongest)
+            .build(patterns);
+        let matches: Vec<(usize, usize, usize)> = automaton
+            .find_all("abcdx abcx abcdex")
+            .iter()
+            .map(|m| (m.pattern_id, m.start, m.end))
+            .collect();
+        assert_eq!(matches, [(3, 0, 4), (0, 6, 8), (1, 8, 9), (2, 11, 16)]);
+    }
+
+    #[test]
+    fn ascii_case_insensitive_works() {
+        let automaton = AhoCorasickBuilder::new()
+            .ascii_case_insensitive(true)
+            .build(["ERROR", "warn"]);
+        let matches: Vec<usize> = automaton
+            .find_all("error: x, Warning: y, ErRoR")
+            .iter()
+            .map(|m| m.pattern_id)
+            .collect();
+        assert_eq!(matches, [0, 1, 0]);
+        assert!(AhoCorasick::new(["ERROR"]).find_all("error").is_empty());
+    }
+
+    #[test]
+    fn matches_equal_brute_force_and_streaming() {
+        let mut rng = XorShift64::new(31);
+        for _ in 0..200 {
+            let random_string = |rng: &mut XorShift64, len: usize| -> String {
+                (0..len)
+                    .map(|_| (b'a' + rng.below(3) as u8) as char)
+                    .collect()
+            };
+            let patterns_count = rng.index(6);
+            let patterns: Vec<String> = (0..patterns_count)
+                .map(|_| {
+                    let len = rng.index(5);
+                    random_string(&mut rng, len)
+                })
+                .collect();
+            let patterns: Vec<&str> = patterns.iter().map(|p| p.as_str()).collect();
+            let haystack_len = rng.index(50);
+            let haystack = random_string(&mut rng, haystack_len);
+
+            for (match_kind, expected) in [
+                (
+                    MatchKind::Overlapping,
+                    brute_force_overlapping(&patterns, &haystack),
+                ),
+                (
+                    MatchKind::LeftmostLongest,
+                    brute_force_leftmost_longest(&patterns, &haystack),
+                ),
+            ] {
+                let automaton = AhoCorasickBuilder::new()
+                    .match_kind(match_kind)
+                    .build(&patterns);
+                assert_eq!(
+                    automaton.find_all(&haystack),
+                    expected,
+                    "{match_kind:?} {patterns:?} {haystack:?}"
+                );
+
+                // the same input split into random chunks
+                let mut stream = automaton.stream();
+                let mut streamed = Vec::new();
+                let mut rest = haystack.as_bytes();
+                while !rest.is_empty() {
+                    let (chunk, tail) = rest.split_at(1 + rng.index(rest.len()).min(7));
+                    streamed.append(&mut stream.feed(chunk));
+                    rest = tail;
+                }
+                streamed.append(&mut stream.finish());
+                assert_eq!(
+                    streamed, expected,
+                    "{match_kind:?} {patterns:?} {haystack:?}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,335 @@
+use crate::data::graph::Graph;
+use crate::data::weight::Weight;
+use crate::search::bellman_ford::{bellman_ford, Relaxation};
+use crate::search::dijkstra::dijkstra_single_source_in_weighted_indexed_graph;
+use crate::search::shortest_path::{DistanceMatrix, NegativeWeightsError};
+
+/// All-pairs shortest paths by Floyd–Warshall in O(V^3), for small and dense graphs.
+/// Edges weights can be negative, returns Err if graph has a negative cycle.
+/// Paths which cost does not fit in W (overflow) are not taken into account.
+pub fn floyd_warshall_in_weighted_indexed_graph<V, W: Weight>(
+    graph: &Graph<V, W>,
+) -> Result<DistanceMatrix<W>, NegativeWeightsError> {
+    let n = graph.nodes.len();
+    let mut distances: Vec<Vec<Option<W>>> = vec![vec![None; n]; n]; // None means infinity
+    let mut previous = vec![vec![None; n]; n];
+    for from in 0..n {
+        distances[from][from] = Some(W::zero());
+        for (to, weight) in &graph.edges[from] {
+            // the cheapest of parallel edges (negative self loop goes below zero)
+            if distances[from][*to].as_ref().is_none_or(|d| weight < d) {
+                distances[from][*to] = Some(weight.clone());
+                previous[from][*to] = Some(from);
+            }
+        }
+    }
+
+    // after k-th round distances[i][j] is the cost of the shortest path with intermediate nodes in 0..=k
+    for k in 0..n {
+        for i in 0..n {
+            let Some(i_k) = distances[i][k].clone() else {
+                continue;
+            };
+            for j in 0..n {
+                let Some(k_j) = &distances[k][j] else {
+                    continue;
+                };
+                let Some(i_k_j) = i_k.checked_add(k_j) else {
+                    if *k_j >= W::zero() {
+                        continue; // too expensive path
+                    }
+                    // path i -> j is cheaper than minimum of W, Bellman–Ford from i tells why
+                    return Err(negative_weights_error_from(graph, i));
+                };
+                if distances[i][j].as_ref().is_none_or(|i_j| i_k_j < *i_j) {
+                    distances[i][j] = Some(i_k_j);
+                    previous[i][j] = previous[k][j];
+                }
+            }
+        }
+        // stop as soon as some node is on a negative cycle, so costs do not keep decreasing
+        if let Some(node) = (0..n).find(|&i| distances[i][i].as_ref() < Some(&W::zero())) {
+            return Err(negative_weights_error_from(graph, node));
+        }
+    }
+
+    Ok(DistanceMatrix {
+        distances,
+        previous,
+    })
+}
+
+/// All-pairs shortest paths by Johnson's algorithm in O(V * E log V), for large and sparse graphs.
+/// Edges are reweighted with Bellman–Ford potentials so they are all non-negative,
+/// then Dijkstra is run from every node.
+/// Edges weights can be negative, returns Err if graph has a negative cycle
+/// or some potential or reweighted edge does not fit in W.
+/// Paths which cost does not fit in W (overflow) are not taken into account.
+pub fn johnson_in_weighted_indexed_graph<V, W: Weight>(
+    graph: &Graph<V, W>,
+) -> Result<DistanceMatrix<W>, NegativeWeightsError> {
+    let n = graph.nodes.len();
+    // potential h(v) is the shortest distance from virtual node with zero-cost edges to all nodes,
+    // i.e. Bellman–Ford started from all nodes at once
+    let all_nodes: Vec<usize> = (0..n).collect();
+    let (potentials, _) = bellman_ford(graph, &all_nodes, Relaxation::Queue)?;
+    let potentials: Vec<W> = potentials.into_iter().map(Option::unwrap).collect();
+
+    // w'(u, v) = w(u, v) + h(u) - h(v) >= 0, and every path u -> v changes its cost by h(u) - h(v)
+    let reweighted = Graph {
+        nodes: vec![(); n],
+        edges: graph
+            .edges
+            .iter()
+            .enumerate()
+            .map(|(from, neighbours)| {
+                neighbours
+                    .iter()
+                    .map(|(to, weight)| {
+                        add_sub(weight, &potentials[from], &potentials[*to])
+                            .map(|weight| (*to, weight))
+                            .ok_or(NegativeWeightsError::Overflow { node: *to })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+    };
+
+    let mut distances = Vec::with_capacity(n);
+    let mut previous = Vec::with_capacity(n);
+    for from in 0..n {
+        let tree = dijkstra_single_source_in_weighted_indexed_graph(&reweighted, from);
+        distances.push(
+            tree.distances
+                .into_iter()
+                .enumerate()
+                .map(|(to, distance)| add_sub(&distance?, &potentials[to], &potentials[from]))
+                .collect(),
+        );
+        previous.push(tree.previous);
+    }
+
+    Ok(DistanceMatrix {
+        distances,
+        previous,
+    })
+}
+
+/// w + a - b, computed in whichever order fits in W (None if the result does not fit).
+fn add_sub<W: Weight>(w: &W, a: &W, b: &W) -> Option<W> {
+    w.checked_add(a)
+        .and_then(|sum| sum.checked_sub(b))
+        .or_else(|| w.checked_sub(b)?.checked_add(a))
+}
+
+/// Negative cycle reachable from node (or negative overflow) found by Bellman–Ford,
+/// when node has negative distance to itself or path from node is cheaper than minimum of W.
+fn negative_weights_error_from<V, W: Weight>(
+    graph: &Graph<V, W>,
+    node: usize,
+) -> NegativeWeightsError {
+    bellman_ford(graph, &[node], Relaxation::Rounds)
+        .expect_err("path from node is cheaper than any representable distance")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::graph::{
+        make_random_weighted_indexed_graph, make_test_weighted_indexed_graph,
+    };
+    use crate::rng::XorShift64;
+    use crate::search::bellman_ford::bellman_ford_single_source_in_weighted_indexed_graph;
+    use crate::search::dijkstra::dijkstra_shortest_path_in_weighted_indexed_graph;
+    use crate::search::shortest_path::NegativeCycle;
+
+    /// Path has edges in graph and its cost is the sum of (the cheapest parallel) edges.
+    fn assert_path_cost(
+        graph: &Graph<usize, i64>,
+        matrix: &DistanceMatrix<i64>,
+        u: usize,
+        v: usize,
+    ) {
+        let Some(path) = matrix.path(u, v) else {
+            return;
+        };
+        assert_eq!((path.nodes[0], *path.nodes.last().unwrap()), (u, v));
+        let cost: i64 = path
+            .nodes
+            .windows(2)
+            .map(|w| {
+                graph.edges[w[0]]
+                    .iter()
+                    .filter(|&&(n, _)| n == w[1])
+                    .map(|&(_, weight)| weight)
+                    .min()
+                    .unwrap()
+            })
+            .sum();
+        assert_eq!(cost, path.cost);
+    }
+
+    #[test]
+    fn all_pairs_work_on_test_graph() {
+        let graph = make_test_weighted_indexed_graph();
+        let floyd_warshall = floyd_warshall_in_weighted_indexed_graph(&graph).unwrap();
+        let johnson = johnson_in_weighted_indexed_graph(&graph).unwrap();
+        for u in 0..8 {
+            for v in 0..8 {
+                let dijkstra = dijkstra_shortest_path_in_weighted_indexed_graph(&graph, u, v);
+                let cost = dijkstra.as_ref().map(|path| path.cost);
+                assert_eq!(floyd_warshall.distance(u, v).copied(), cost);
+                assert_eq!(johnson.distance(u, v).copied(), cost);
+                assert_eq!(johnson.path(u, v), dijkstra);
+            }
+        }
+        assert_eq!(floyd_warshall.path(1, 5).unwrap().nodes, [1, 0, 4, 5]);
+    }
+
+    #[test]
+    fn all_pairs_work_with_negative_weights() {
+        // the same graph as in Bellman–Ford test: 0 -> 2 -> 1 -> 3 is cheaper than 0 -> 1 -> 3
+        let graph = Graph {
+            nodes: vec![0, 1, 2, 3],
+            edges: vec![vec![(1, 1i64), (2, 3)], vec![(3, 1)], vec![(1, -3)], vec![]],
+        };
+        for matrix in [
+            floyd_warshall_in_weighted_indexed_graph(&graph).unwrap(),
+            johnson_in_weighted_indexed_graph(&graph).unwrap(),
+        ] {
+            assert_eq!(matrix.path(0, 3).unwrap().nodes, [0, 2, 1, 3]);
+            assert_eq!(matrix.distance(0, 3), Some(&1));
+            assert_eq!(matrix.distance(2, 3), Some(&-2));
+            assert_eq!(matrix.distance(3, 0), None);
+            assert_eq!(matrix.path(3, 0), None);
+            assert_eq!(matrix.path(2, 2).unwrap().nodes, [2]);
+        }
+    }
+
+    #[test]
+    fn all_pairs_report_negative_cycle() {
+        // 1 -> 2 -> 3 -> 1 costs -1, it is found also when not reachable from 0
+        let graph = Graph {
+            nodes: vec![0, 1, 2, 3],
+            edges: vec![vec![], vec![(2, 1i64)], vec![(3, -4)], vec![(1, 2)]],
+        };
+        for error in [
+            floyd_warshall_in_weighted_indexed_graph(&graph).unwrap_err(),
+            johnson_in_weighted_indexed_graph(&graph).unwrap_err(),
+        ] {
+            let NegativeWeightsError::NegativeCycle(NegativeCycle { mut nodes }) = error else {
+                panic!("{error:?} is not a negative cycle");
+            };
+            nodes.sort();
+            assert_eq!(nodes, [1, 2, 3]);
+        }
+
+        let self_loop = Graph {
+            nodes: vec![0],
+            edges: vec![vec![(0, -1i64)]],
+        };
+        assert_eq!(
+            floyd_warshall_in_weighted_indexed_graph(&self_loop).unwrap_err(),
+            NegativeWeightsError::NegativeCycle(NegativeCycle { nodes: vec![0] })
+        );
+        assert_eq!(
+            johnson_in_weighted_indexed_graph(&self_loop).unwrap_err(),
+            NegativeWeightsError::NegativeCycle(NegativeCycle { nodes: vec![0] })
+        );
+    }
+
+    #[test]
+    fn all_pairs_report_overflow() {
+        assert_eq!(add_sub(&100i8, &50, &40), Some(110));
+        assert_eq!(add_sub(&-100i8, &-50, &-40), Some(-110));
+        assert_eq!(add_sub(&100i8, &0, &-100), None);
+
+        // going around the cycle once costs -200, which does not fit in i8
+        let cycle = Graph {
+            nodes: vec![0, 1],
+            edges: vec![vec![(1, -100i8)], vec![(0, -100)]],
+        };
+        // path 0 -> 1 -> 2 costs -200
+        let chain = Graph {
+            nodes: vec![0, 1, 2],
+            edges: vec![vec![(1, -100i8)], vec![(2, -100)], vec![]],
+        };
+        for result in [
+            floyd_warshall_in_weighted_indexed_graph(&cycle),
+            johnson_in_weighted_indexed_graph(&cycle),
+        ] {
+            let Err(NegativeWeightsError::NegativeCycle(NegativeCycle { mut nodes })) = result
+            else {
+                panic!("{result:?} is not a negative cycle");
+            };
+            nodes.sort();
+            assert_eq!(nodes, [0, 1]);
+        }
+        for result in [
+            floyd_warshall_in_weighted_indexed_graph(&chain),
+            johnson_in_weighted_indexed_graph(&chain),
+        ] {
+            assert_eq!(result, Err(NegativeWeightsError::Overflow { node: 2 }));
+        }
+
+        // reweighted edge 0 -> 1 is 100 + 0 - (-100), which does not fit in i8,
+        // so Johnson cannot find distance 0 -> 1, but it does not drop the edge
+        let graph = Graph {
+            nodes: vec![0, 1, 2],
+            edges: vec![vec![(1, 100i8)], vec![], vec![(1, -100)]],
+        };
+        assert_eq!(
+            johnson_in_weighted_indexed_graph(&graph),
+            Err(NegativeWeightsError::Overflow { node: 1 })
+        );
+        assert_eq!(
+            floyd_warshall_in_weighted_indexed_graph(&graph)
+                .unwrap()
+                .distance(0, 1),
+            Some(&100)
+        );
+    }
+
+    #[test]
+    fn all_pairs_agree_with_bellman_ford_on_random_graphs() {
+        let mut rng = XorShift64::new(38);
+        for _ in 0..30 {
+            let n = 1 + rng.index(20);
+            let graph = make_random_weighted_indexed_graph(&mut rng, n, 3 * n, 10);
+            // w(u, v) + p(v) - p(u) has negative edges, but no negative cycles
+            // (cost of every cycle is unchanged)
+            let potentials: Vec<i64> = (0..n).map(|_| rng.below(20) as i64).collect();
+            let graph = Graph {
+                nodes: graph.nodes,
+                edges: graph
+                    .edges
+                    .iter()
+                    .enumerate()
+                    .map(|(from, neighbours)| {
+                        neighbours
+                            .iter()
+                            .map(|&(to, w)| (to, w as i64 + potentials[to] - potentials[from]))
+                            .collect()
+                    })
+                    .collect(),
+            };
+
+            let floyd_warshall = floyd_warshall_in_weighted_indexed_graph(&graph).unwrap();
+            let johnson = johnson_in_weighted_indexed_graph(&graph).unwrap();
+            assert_eq!(floyd_warshall.distances, johnson.distances);
+            for u in 0..n {
+                let tree = bellman_ford_single_source_in_weighted_indexed_graph(
+                    &graph,
+                    u,
+                    Relaxation::Rounds,
+                )
+                .unwrap();
+                assert_eq!(floyd_warshall.distances[u], tree.distances);
+                for v in 0..n {
+                    assert_path_cost(&graph, &floyd_warshall, u, v);
+                    assert_path_cost(&graph, &johnson, u, v);
+                }
+            }
+        }
+    }
+}
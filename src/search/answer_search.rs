@@ -0,0 +1,237 @@
+//! Binary search on the answer: searching over monotone predicates and over real intervals.
+
+use std::ops::Range;
+
+/// Integer types which can be searched over by `search_min_true`/`search_max_true`.
+pub trait SearchInteger: Copy + Ord {
+    /// Middle point of left..right (left < right), computed without overflow.
+    fn midpoint_of(left: Self, right: Self) -> Self;
+    fn succ(self) -> Self;
+    fn pred(self) -> Self;
+}
+
+macro_rules! impl_search_integer {
+    ($($t:ty),*) => {
+        $(
+            impl SearchInteger for $t {
+                fn midpoint_of(left: Self, right: Self) -> Self {
+                    // left + (right - left) / 2 can overflow for signed types (e.g. i64::MIN..i64::MAX),
+                    // so distance is computed in unsigned type
+                    left + (right.abs_diff(left) / 2) as $t
+                }
+                fn succ(self) -> Self {
+                    self + 1
+                }
+                fn pred(self) -> Self {
+                    self - 1
+                }
+            }
+        )*
+    };
+}
+impl_search_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Find the smallest x in lo..hi for which pred(x) holds.
+/// Predicate must be monotone in range: [false .. false | true .. true].
+/// Returns None if pred does not hold for any x in range.
+/// Never overflows, also for ranges close to MIN/MAX of the integer type.
+pub fn search_min_true<T, P>(range: Range<T>, mut pred: P) -> Option<T>
+where
+    T: SearchInteger,
+    P: FnMut(T) -> bool,
+{
+    // invariant: pred is false for range.start..left and true for right..range.end
+    let mut left = range.start;
+    let mut right = range.end;
+    while left < right {
+        let mid = T::midpoint_of(left, right); // left <= mid < right
+        if pred(mid) {
+            right = mid;
+        } else {
+            left = mid.succ();
+        }
+    }
+
+    (left < range.end).then_some(left)
+}
+
+/// Find the largest x in lo..hi for which pred(x) holds.
+/// Predicate must be monotone in range: [true .. true | false .. false].
+pub fn search_max_true<T, P>(range: Range<T>, mut pred: P) -> Option<T>
+where
+    T: SearchInteger,
+    P: FnMut(T) -> bool,
+{
+    let first_false = search_min_true(range.clone(), |x| !pred(x)).unwrap_or(range.end);
+    (first_false > range.start).then(|| first_false.pred())
+}
+
+/// Limits of searching over real interval.
+#[derive(Clone, Copy, Debug)]
+pub struct Tolerance {
+    /// Stop when the interval is not wider than this.
+    pub epsilon: f64,
+    /// Stop after this many iterations (even if epsilon is not reached).
+    pub max_iterations: usize,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Tolerance {
+            epsilon: 1e-9,
+            max_iterations: 200,
+        }
+    }
+}
+
+/// Bisection over real interval [lo, hi] for monotone predicate: [false .. false | true .. true].
+/// Returns approximation of the smallest x for which pred(x) holds (i.e. upper end of the final interval),
+/// or None if pred(hi) does not hold.
+pub fn bisection<P>(lo: f64, hi: f64, tolerance: Tolerance, mut pred: P) -> Option<f64>
+where
+    P: FnMut(f64) -> bool,
+{
+    if !pred(hi) {
+        return None;
+    }
+    let (mut lo, mut hi) = (lo, hi);
+    for _ in 0..tolerance.max_iterations {
+        if hi - lo <= tolerance.epsilon {
+            break;
+        }
+        let mid = lo + (hi - lo) / 2.0;
+        if pred(mid) {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    Some(hi)
+}
+
+/// Ternary search for x in [lo, hi] which minimizes unimodal function f.
+pub fn ternary_search_min<F>(lo: f64, hi: f64, tolerance: Tolerance, mut f: F) -> f64
+where
+    F: FnMut(f64) -> f64,
+{
+    let (mut lo, mut hi) = (lo, hi);
+    for _ in 0..tolerance.max_iterations {
+        if hi - lo <= tolerance.epsilon {
+            break;
+        }
+        let m1 = lo + (hi - lo) / 3.0;
+        let m2 = hi - (hi - lo) / 3.0;
+        if f(m1) < f(m2) {
+            hi = m2;
+        } else {
+            lo = m1;
+        }
+    }
+    lo + (hi - lo) / 2.0
+}
+
+/// Golden-section search for x in [lo, hi] which minimizes unimodal function f.
+/// Like ternary search, but reuses one of inner points, so needs only one evaluation of f per iteration.
+pub fn golden_section_search_min<F>(lo: f64, hi: f64, tolerance: Tolerance, mut f: F) -> f64
+where
+    F: FnMut(f64) -> f64,
+{
+    // 1/phi, where phi is the golden ratio
+    let inv_phi = (5f64.sqrt() - 1.0) / 2.0;
+
+    let (mut lo, mut hi) = (lo, hi);
+    // inner points: lo < x1 < x2 < hi
+    let mut x1 = hi - inv_phi * (hi - lo);
+    let mut x2 = lo + inv_phi * (hi - lo);
+    let mut f1 = f(x1);
+    let mut f2 = f(x2);
+    for _ in 0..tolerance.max_iterations {
+        if hi - lo <= tolerance.epsilon {
+            break;
+        }
+        if f1 < f2 {
+            // minimum is in [lo, x2], old x1 becomes new x2
+            hi = x2;
+            x2 = x1;
+            f2 = f1;
+            x1 = hi - inv_phi * (hi - lo);
+            f1 = f(x1);
+        } else {
+            // minimum is in [x1, hi], old x2 becomes new x1
+            lo = x1;
+            x1 = x2;
+            f1 = f2;
+            x2 = lo + inv_phi * (hi - lo);
+            f2 = f(x2);
+        }
+    }
+    lo + (hi - lo) / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_min_true_works() {
+        // smallest x, such that x * x >= 1000
+        assert_eq!(search_min_true(0..1000, |x| x * x >= 1000), Some(32));
+        assert_eq!(search_min_true(0..10, |_| true), Some(0));
+        assert_eq!(search_min_true(0..10, |_| false), None);
+        assert_eq!(search_min_true(5..5, |_| true), None);
+        assert_eq!(search_min_true(-50..50, |x| x >= -7), Some(-7));
+        assert_eq!(search_min_true(0u8..255, |x| x >= 200), Some(200));
+
+        // no overflow on extreme ranges
+        assert_eq!(
+            search_min_true(i64::MIN..i64::MAX, |x| x >= i64::MAX - 1),
+            Some(i64::MAX - 1)
+        );
+        assert_eq!(
+            search_min_true(i64::MIN..i64::MAX, |_| true),
+            Some(i64::MIN)
+        );
+        assert_eq!(search_min_true(i64::MIN..i64::MAX, |_| false), None);
+    }
+
+    #[test]
+    fn search_max_true_works() {
+        // largest x, such that x * x <= 1000
+        assert_eq!(search_max_true(0..1000, |x| x * x <= 1000), Some(31));
+        assert_eq!(search_max_true(0..10, |_| true), Some(9));
+        assert_eq!(search_max_true(0..10, |_| false), None);
+        assert_eq!(
+            search_max_true(i64::MIN..i64::MAX, |_| true),
+            Some(i64::MAX - 1)
+        );
+    }
+
+    #[test]
+    fn real_interval_search_works() {
+        let tolerance = Tolerance::default();
+
+        let sqrt2 = bisection(0.0, 2.0, tolerance, |x| x * x >= 2.0).unwrap();
+        assert!((sqrt2 - 2f64.sqrt()).abs() < 1e-8);
+        assert_eq!(bisection(0.0, 1.0, tolerance, |x| x > 5.0), None);
+
+        let f = |x: f64| (x - 1.25) * (x - 1.25) + 3.0;
+        let x = ternary_search_min(-10.0, 10.0, tolerance, f);
+        assert!((x - 1.25).abs() < 1e-6);
+
+        let mut evaluations = 0;
+        let x = golden_section_search_min(-10.0, 10.0, tolerance, |x| {
+            evaluations += 1;
+            f(x)
+        });
+        assert!((x - 1.25).abs() < 1e-6);
+        assert!(evaluations < 60, "evaluations: {evaluations}");
+
+        // iteration limit stops search before epsilon is reached
+        let limited = Tolerance {
+            epsilon: 0.0,
+            max_iterations: 3,
+        };
+        let sqrt2 = bisection(0.0, 2.0, limited, |x| x * x >= 2.0).unwrap();
+        assert_eq!(sqrt2, 1.5);
+    }
+}
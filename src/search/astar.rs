@@ -0,0 +1,317 @@
+use crate::data::graph::{Graph, GraphNodeIndex};
+use crate::data::weight::{OrderedFloat, Weight};
+use crate::search::maze::Position;
+use crate::search::shortest_path::{reconstruct_path, ShortestPath};
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+/// Result of A* search: the shortest path (comparable with BFS and Dijkstra results)
+/// and number of nodes taken from the open set and expanded (i.e. their neighbours checked).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AStarPath<W> {
+    pub path: ShortestPath<GraphNodeIndex, W>,
+    pub expanded: usize,
+}
+
+struct Estimate<W> {
+    index: GraphNodeIndex,
+    // cost from start
+    cost: W,
+    // cost from start + heuristic estimate of cost to target
+    estimate: W,
+}
+impl<W: Weight> PartialEq for Estimate<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl<W: Weight> PartialOrd for Estimate<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<W: Weight> Ord for Estimate<W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // min-heap by estimate, on ties prefer nodes further from start (i.e. closer to target)
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then(self.cost.cmp(&other.cost))
+    }
+}
+impl<W: Weight> Eq for Estimate<W> {}
+
+/// Search for shortest path by using A*: Dijkstra which follows the nodes with the smallest
+/// (cost from start + heuristic estimate of the cost to target) first.
+/// - edge_cost gives (non negative) cost of edge
+/// - heuristic estimates cost from node to target; path is the shortest if it never overestimates (is admissible)
+///
+/// Paths which cost does not fit in W (overflow) are not taken into account.
+/// Returns None when target is not reachable from start.
+pub fn astar_shortest_path_in_indexed_graph<V, E, W, C, H>(
+    graph: &Graph<V, E>,
+    start: GraphNodeIndex,
+    target: GraphNodeIndex,
+    edge_cost: C,
+    heuristic: H,
+) -> Option<AStarPath<W>>
+where
+    W: Weight,
+    C: Fn(&E) -> W,
+    H: Fn(GraphNodeIndex) -> W,
+{
+    let mut costs: Vec<Option<W>> = vec![None; graph.nodes.len()]; // None means infinity
+    let mut previous = vec![None; graph.nodes.len()];
+    let mut heap = BinaryHeap::new(); // open set
+    let mut expanded = 0;
+
+    costs[start] = Some(W::zero());
+    heap.push(Estimate {
+        index: start,
+        cost: W::zero(),
+        estimate: heuristic(start),
+    });
+
+    while let Some(Estimate {
+        index: current,
+        cost: current_cost,
+        ..
+    }) = heap.pop()
+    {
+        if costs[current]
+            .as_ref()
+            .is_some_and(|cost| current_cost > *cost)
+        {
+            continue; // outdated entry, cheaper path to current was already expanded
+        }
+        expanded += 1;
+        if current == target {
+            break;
+        }
+
+        for (neighbour, edge) in &graph.edges[current] {
+            let neighbour = *neighbour;
+            let Some(neighbour_cost) = current_cost.checked_add(&edge_cost(edge)) else {
+                continue; // overflow
+            };
+            if costs[neighbour]
+                .as_ref()
+                .is_some_and(|cost| neighbour_cost >= *cost)
+            {
+                continue;
+            }
+            // admissible heuristic does not exceed the rest of path, so whole path would overflow
+            let Some(estimate) = neighbour_cost.checked_add(&heuristic(neighbour)) else {
+                continue;
+            };
+            costs[neighbour] = Some(neighbour_cost.clone());
+            previous[neighbour] = Some(current);
+            heap.push(Estimate {
+                index: neighbour,
+                cost: neighbour_cost,
+                estimate,
+            });
+        }
+    }
+
+    let cost = costs[target].clone()?;
+    Some(AStarPath {
+        path: ShortestPath {
+            nodes: reconstruct_path(&previous, target),
+            cost,
+        },
+        expanded,
+    })
+}
+
+fn distances(a: Position, b: Position) -> (f64, f64) {
+    (a.i.abs_diff(b.i) as f64, a.j.abs_diff(b.j) as f64)
+}
+
+// Heuristics return OrderedFloat (a Weight), so they can be passed to A* with float edge costs.
+
+/// Heuristic for grids with up/down/left/right moves of cost 1.
+pub fn manhattan(a: Position, b: Position) -> OrderedFloat<f64> {
+    let (di, dj) = distances(a, b);
+    OrderedFloat(di + dj)
+}
+
+/// Heuristic for any movement, as straight line distance.
+pub fn euclidean(a: Position, b: Position) -> OrderedFloat<f64> {
+    let (di, dj) = distances(a, b);
+    OrderedFloat(di.hypot(dj))
+}
+
+/// Heuristic for grids with 8 directions moves, all of cost 1.
+pub fn chebyshev(a: Position, b: Position) -> OrderedFloat<f64> {
+    let (di, dj) = distances(a, b);
+    OrderedFloat(di.max(dj))
+}
+
+/// Heuristic for grids with 8 directions moves: straight of cost 1 and diagonal of cost sqrt(2).
+pub fn octile(a: Position, b: Position) -> OrderedFloat<f64> {
+    let (di, dj) = distances(a, b);
+    OrderedFloat(di.max(dj) + (2f64.sqrt() - 1.0) * di.min(dj))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::graph::{make_test_weighted_indexed_graph, Graph};
+    use crate::search::{
+        bfs::bfs_shortest_path_in_indexed_graph,
+        dijkstra::dijkstra_shortest_path_in_weighted_indexed_graph,
+        maze::construct_test_maze_as_graph,
+    };
+
+    #[test]
+    fn heuristics_work() {
+        let (a, b) = (Position::new(1, 2), Position::new(4, 6));
+        assert_eq!(manhattan(a, b), OrderedFloat(7.0));
+        assert_eq!(euclidean(a, b), OrderedFloat(5.0));
+        assert_eq!(chebyshev(a, b), OrderedFloat(4.0));
+        assert!((octile(a, b).0 - (1.0 + 3.0 * 2f64.sqrt())).abs() < 1e-12);
+        assert_eq!(manhattan(a, a), OrderedFloat::zero());
+    }
+
+    // Maze:
+    //        10100010<-end
+    //        00100100
+    //        01101101
+    //        01100101
+    //        01110101
+    //        00110100
+    //        10000010
+    // start->00111000
+    #[test]
+    fn astar_solves_maze_like_bfs() {
+        let maze = construct_test_maze_as_graph();
+        let maze_width = 8;
+        let start = Position::new(7, 0).index(maze_width);
+        let end_position = Position::new(0, 7);
+        let end = end_position.index(maze_width);
+
+        let bfs_path = bfs_shortest_path_in_indexed_graph(&maze, start, end).unwrap();
+
+        let mut expanded_by_heuristic = Vec::new();
+        for heuristic in [manhattan, euclidean, chebyshev, octile] {
+            let astar = astar_shortest_path_in_indexed_graph(
+                &maze,
+                start,
+                end,
+                |_| OrderedFloat(1.0),
+                |index| heuristic(Position::from_index(index, maze_width), end_position),
+            )
+            .unwrap();
+            assert_eq!(astar.path.nodes.len(), bfs_path.nodes.len());
+            assert_eq!(astar.path.cost, OrderedFloat(bfs_path.cost as f64));
+            for w in astar.path.nodes.windows(2) {
+                assert!(maze.edges[w[0]].iter().any(|&(n, _)| n == w[1]));
+            }
+            expanded_by_heuristic.push(astar.expanded);
+        }
+
+        // without heuristic A* is Dijkstra, and expands more nodes than with Manhattan distance
+        let dijkstra_like =
+            astar_shortest_path_in_indexed_graph(&maze, start, end, |_| 1usize, |_| 0).unwrap();
+        assert_eq!(dijkstra_like.path.cost, bfs_path.cost);
+        assert!(expanded_by_heuristic[0] <= dijkstra_like.expanded);
+
+        // wall is not reachable
+        let wall = Position::new(0, 0).index(maze_width);
+        assert_eq!(
+            astar_shortest_path_in_indexed_graph(&maze, start, wall, |_| 1usize, |_| 0),
+            None
+        );
+    }
+
+    #[test]
+    fn astar_with_zero_heuristic_equals_dijkstra() {
+        let graph = make_test_weighted_indexed_graph();
+        for (start, target) in [(0, 5), (1, 5), (0, 7), (6, 3)] {
+            let astar =
+                astar_shortest_path_in_indexed_graph(&graph, start, target, |&w| w, |_| 0).unwrap();
+            let dijkstra_path =
+                dijkstra_shortest_path_in_weighted_indexed_graph(&graph, start, target).unwrap();
+            assert_eq!(astar.path.cost, dijkstra_path.cost);
+            // float costs
+            let float = astar_shortest_path_in_indexed_graph(
+                &graph,
+                start,
+                target,
+                |&w| OrderedFloat(w as f64),
+                |_| OrderedFloat(0.0),
+            )
+            .unwrap();
+            assert_eq!(float.path.cost, OrderedFloat(dijkstra_path.cost as f64));
+        }
+    }
+
+    /// Open 8x8 grid with a wall, where every node is connected to its (up to 8) free neighbours:
+    /// straight moves cost 1, diagonal moves cost sqrt(2).
+    fn construct_diagonal_maze_as_graph() -> Graph<Position, OrderedFloat<f64>> {
+        let width = 8;
+        let is_wall = |p: Position| p.j == 4 && p.i > 0;
+        let nodes: Vec<Position> = (0..width * width)
+            .map(|index| Position::from_index(index, width))
+            .collect();
+        let edges = nodes
+            .iter()
+            .map(|&p| {
+                let mut neighbours = Vec::new();
+                if is_wall(p) {
+                    return neighbours;
+                }
+                for i in p.i.saturating_sub(1)..=(p.i + 1).min(width - 1) {
+                    for j in p.j.saturating_sub(1)..=(p.j + 1).min(width - 1) {
+                        let neighbour = Position::new(i, j);
+                        if neighbour != p && !is_wall(neighbour) {
+                            let diagonal = i != p.i && j != p.j;
+                            let cost = if diagonal { 2f64.sqrt() } else { 1.0 };
+                            neighbours.push((neighbour.index(width), OrderedFloat(cost)));
+                        }
+                    }
+                }
+                neighbours
+            })
+            .collect();
+        Graph { nodes, edges }
+    }
+
+    #[test]
+    fn astar_with_octile_heuristic_solves_diagonal_maze() {
+        let maze = construct_diagonal_maze_as_graph();
+        let maze_width = 8;
+        let end_position = Position::new(7, 7);
+        let (start, end) = (
+            Position::new(7, 0).index(maze_width),
+            end_position.index(maze_width),
+        );
+
+        let dijkstra_path =
+            dijkstra_shortest_path_in_weighted_indexed_graph(&maze, start, end).unwrap();
+        let dijkstra_like = astar_shortest_path_in_indexed_graph(
+            &maze,
+            start,
+            end,
+            |&cost| cost,
+            |_| OrderedFloat::zero(),
+        )
+        .unwrap();
+
+        for heuristic in [octile, euclidean] {
+            let astar = astar_shortest_path_in_indexed_graph(
+                &maze,
+                start,
+                end,
+                |&cost| cost,
+                |index| heuristic(Position::from_index(index, maze_width), end_position),
+            )
+            .unwrap();
+            // admissible heuristic finds the shortest path (up to rounding of float sums)
+            assert!((astar.path.cost.0 - dijkstra_path.cost.0).abs() < 1e-9);
+            assert!(astar.expanded <= dijkstra_like.expanded);
+        }
+        // up to the gap above the wall (4 diagonal and 3 straight moves) and down (3 diagonal, 4 straight)
+        assert!((dijkstra_path.cost.0 - (7.0 + 7.0 * 2f64.sqrt())).abs() < 1e-9);
+    }
+}
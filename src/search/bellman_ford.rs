@@ -0,0 +1,393 @@
+use crate::data::graph::{Graph, GraphNodeIndex};
+use crate::data::weight::Weight;
+use crate::search::shortest_path::{
+    predecessor_cycle, NegativeWeightsError, ShortestPath, ShortestPathTree,
+};
+use std::collections::VecDeque;
+
+/// Order in which Bellman–Ford relaxes edges.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Relaxation {
+    /// Classic Bellman–Ford: relax all edges, in up to n - 1 rounds, O(V * E).
+    #[default]
+    Rounds,
+    /// SPFA (Shortest Path Faster Algorithm): relax only edges of nodes which distance has changed,
+    /// kept in FIFO queue. The same O(V * E) worst case, but usually much faster.
+    Queue,
+}
+
+/// Search for the cheapest path in graph where edges are weights, which can be negative.
+/// Returns Err if there is a negative cycle reachable from start (even if target is not on it)
+/// or cost of some path goes below the minimum of W, otherwise None if target is not reachable.
+pub fn bellman_ford_shortest_path_in_weighted_indexed_graph<V, W: Weight>(
+    graph: &Graph<V, W>,
+    start: GraphNodeIndex,
+    target: GraphNodeIndex,
+    relaxation: Relaxation,
+) -> Result<Option<ShortestPath<GraphNodeIndex, W>>, NegativeWeightsError> {
+    let tree = bellman_ford_single_source_in_weighted_indexed_graph(graph, start, relaxation)?;
+    Ok(tree.path(target))
+}
+
+/// The cheapest paths from start to all nodes in graph where edges are weights, which can be negative.
+/// Returns Err with the cycle if there is a negative cycle reachable from start,
+/// or Err(Overflow) if cost of some path goes below the minimum of W (without negative cycle found).
+pub fn bellman_ford_single_source_in_weighted_indexed_graph<V, W: Weight>(
+    graph: &Graph<V, W>,
+    start: GraphNodeIndex,
+    relaxation: Relaxation,
+) -> Result<ShortestPathTree<W>, NegativeWeightsError> {
+    let (distances, previous) = bellman_ford(graph, &[start], relaxation)?;
+    Ok(ShortestPathTree {
+        start,
+        distances,
+        previous,
+    })
+}
+
+/// (distances, previous) indexed by GraphNodeIndex, as in ShortestPathTree.
+pub(crate) type DistancesAndPrevious<W> = (Vec<Option<W>>, Vec<Option<GraphNodeIndex>>);
+
+/// Bellman–Ford from all 'starts' at once (each with distance zero).
+/// Returns (distances, previous), or Err with reachable negative cycle (or negative overflow).
+/// Paths which cost is above the maximum of W (overflow) are not taken into account.
+pub(crate) fn bellman_ford<V, W: Weight>(
+    graph: &Graph<V, W>,
+    starts: &[GraphNodeIndex],
+    relaxation: Relaxation,
+) -> Result<DistancesAndPrevious<W>, NegativeWeightsError> {
+    let n = graph.nodes.len();
+    let mut distances: Vec<Option<W>> = vec![None; n]; // None means infinity
+    let mut previous = vec![None; n];
+    for &start in starts {
+        distances[start] = Some(W::zero());
+    }
+
+    // relax edges of 'current', calls 'relaxed' for every neighbour which distance has decreased
+    let relax = |distances: &mut Vec<Option<W>>,
+                 previous: &mut Vec<Option<GraphNodeIndex>>,
+                 current: GraphNodeIndex,
+                 relaxed: &mut dyn FnMut(GraphNodeIndex)|
+     -> Result<(), NegativeWeightsError> {
+        let Some(current_distance) = distances[current].clone() else {
+            return Ok(());
+        };
+        for (neighbour, weight) in &graph.edges[current] {
+            let Some(neighbour_distance) = current_distance.checked_add(weight) else {
+                if *weight >= W::zero() {
+                    continue; // too expensive path
+                }
+                // path is cheaper than any distance which can be represented, e.g. it keeps going
+                // around a negative cycle, which is then on predecessor links
+                previous[*neighbour] = Some(current);
+                return Err(match predecessor_cycle(previous, *neighbour) {
+                    Some(cycle) => NegativeWeightsError::NegativeCycle(cycle),
+                    None => NegativeWeightsError::Overflow { node: *neighbour },
+                });
+            };
+            if distances[*neighbour]
+                .as_ref()
+                .is_none_or(|distance| neighbour_distance < *distance)
+            {
+                distances[*neighbour] = Some(neighbour_distance);
+                previous[*neighbour] = Some(current);
+                relaxed(*neighbour);
+            }
+        }
+        Ok(())
+    };
+
+    match relaxation {
+        Relaxation::Rounds => {
+            // without negative cycles the shortest paths have at most n - 1 edges,
+            // so n-th round which still changes something proves that there is a negative cycle
+            for _ in 0..n {
+                let mut changed = None;
+                for current in 0..n {
+                    relax(&mut distances, &mut previous, current, &mut |neighbour| {
+                        changed = Some(neighbour)
+                    })?;
+                }
+                let Some(changed) = changed else {
+                    return Ok((distances, previous));
+                };
+                if let Some(cycle) = predecessor_cycle(&previous, changed) {
+                    return Err(NegativeWeightsError::NegativeCycle(cycle));
+                }
+            }
+            // predecessor links of nodes changed in the n-th round contain a negative cycle
+            let cycle = (0..n)
+                .find_map(|node| predecessor_cycle(&previous, node))
+                .expect("n-th round changed distances, so predecessors have a cycle");
+            Err(NegativeWeightsError::NegativeCycle(cycle))
+        }
+        Relaxation::Queue => {
+            let mut queue: VecDeque<GraphNodeIndex> = starts.iter().copied().collect();
+            let mut in_queue = vec![false; n];
+            // number of edges of the current path to node,
+            // path with n edges has a cycle, which has to be negative
+            let mut edges_count = vec![0; n];
+            for &start in starts {
+                in_queue[start] = true;
+            }
+            while let Some(current) = queue.pop_front() {
+                in_queue[current] = false;
+                let mut relaxed = Vec::new();
+                relax(&mut distances, &mut previous, current, &mut |neighbour| {
+                    relaxed.push(neighbour)
+                })?;
+                for neighbour in relaxed {
+                    edges_count[neighbour] = edges_count[current] + 1;
+                    if edges_count[neighbour] >= n {
+                        // predecessor links can be already updated further,
+                        // if they do not lead to the cycle it will be detected later
+                        if let Some(cycle) = predecessor_cycle(&previous, neighbour) {
+                            return Err(NegativeWeightsError::NegativeCycle(cycle));
+                        }
+                    }
+                    if !in_queue[neighbour] {
+                        in_queue[neighbour] = true;
+                        queue.push_back(neighbour);
+                    }
+                }
+            }
+            Ok((distances, previous))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::graph::{
+        make_random_weighted_indexed_graph, make_test_weighted_indexed_graph,
+    };
+    use crate::rng::XorShift64;
+    use crate::search::dijkstra::{
+        dijkstra_shortest_path_in_weighted_indexed_graph,
+        dijkstra_single_source_in_weighted_indexed_graph,
+    };
+
+    const RELAXATIONS: [Relaxation; 2] = [Relaxation::Rounds, Relaxation::Queue];
+
+    /// Error is a cycle which has edges in graph and negative total cost, returns nodes of cycle.
+    fn assert_negative_cycle<W>(graph: &Graph<usize, W>, error: &NegativeWeightsError) -> Vec<usize>
+    where
+        W: Copy + Ord + Into<i64>,
+    {
+        let NegativeWeightsError::NegativeCycle(cycle) = error else {
+            panic!("{error:?} is not a negative cycle");
+        };
+        let nodes = &cycle.nodes;
+        assert!(!nodes.is_empty());
+        let cost: i64 = (0..nodes.len())
+            .map(|i| {
+                let (from, to) = (nodes[i], nodes[(i + 1) % nodes.len()]);
+                graph.edges[from]
+                    .iter()
+                    .filter(|&&(n, _)| n == to)
+                    .map(|&(_, w)| w.into())
+                    .min()
+                    .expect("cycle edge is in graph")
+            })
+            .sum();
+        assert!(cost < 0, "{nodes:?} costs {cost}");
+        nodes.clone()
+    }
+
+    #[test]
+    fn bellman_ford_works_with_negative_weights() {
+        //    0 --4--> 1 --(-3)--> 3
+        //    |        ^
+        //    1        5
+        //    v        |
+        //    2 --------
+        // 4 is not reachable
+        let graph = Graph {
+            nodes: vec![0, 1, 2, 3, 4],
+            edges: vec![
+                vec![(1, 4i64), (2, 1)],
+                vec![(3, -3)],
+                vec![(1, 5)],
+                vec![],
+                vec![(0, -10)],
+            ],
+        };
+        for relaxation in RELAXATIONS {
+            let path =
+                bellman_ford_shortest_path_in_weighted_indexed_graph(&graph, 0, 3, relaxation)
+                    .unwrap()
+                    .unwrap();
+            assert_eq!(path.nodes, [0, 1, 3]);
+            assert_eq!(path.cost, 1);
+
+            let tree = bellman_ford_single_source_in_weighted_indexed_graph(&graph, 0, relaxation)
+                .unwrap();
+            assert_eq!(tree.distances, [Some(0), Some(4), Some(1), Some(1), None]);
+            assert_eq!(tree.path(4), None);
+        }
+
+        // rebate makes longer path cheaper, which Dijkstra does not see
+        let graph = Graph {
+            nodes: vec![0, 1, 2, 3],
+            edges: vec![vec![(1, 1i64), (2, 3)], vec![(3, 1)], vec![(1, -3)], vec![]],
+        };
+        for relaxation in RELAXATIONS {
+            let path =
+                bellman_ford_shortest_path_in_weighted_indexed_graph(&graph, 0, 3, relaxation)
+                    .unwrap()
+                    .unwrap();
+            assert_eq!(path.nodes, [0, 2, 1, 3]);
+            assert_eq!(path.cost, 1);
+        }
+    }
+
+    #[test]
+    fn bellman_ford_reports_negative_cycle() {
+        // 0 -> 1 -> 2 -> 3 -> 1 is a negative cycle, 4 -> 5 -> 4 is negative but not reachable from 0
+        let graph = Graph {
+            nodes: vec![0, 1, 2, 3, 4, 5],
+            edges: vec![
+                vec![(1, 2i64)],
+                vec![(2, 1)],
+                vec![(3, -4)],
+                vec![(1, 2)],
+                vec![(5, -1)],
+                vec![(4, -1)],
+            ],
+        };
+        for relaxation in RELAXATIONS {
+            let cycle = bellman_ford_single_source_in_weighted_indexed_graph(&graph, 0, relaxation)
+                .unwrap_err();
+            let mut nodes = assert_negative_cycle(&graph, &cycle);
+            nodes.sort();
+            assert_eq!(nodes, [1, 2, 3]);
+
+            let cycle = bellman_ford_single_source_in_weighted_indexed_graph(&graph, 4, relaxation)
+                .unwrap_err();
+            assert_negative_cycle(&graph, &cycle);
+
+            // unreachable negative cycle does not matter
+            let mut graph = graph.edges.clone();
+            graph[3].clear();
+            let graph = Graph {
+                nodes: vec![0, 1, 2, 3, 4, 5],
+                edges: graph,
+            };
+            let path =
+                bellman_ford_shortest_path_in_weighted_indexed_graph(&graph, 0, 3, relaxation)
+                    .unwrap()
+                    .unwrap();
+            assert_eq!(path.cost, -1);
+        }
+    }
+
+    #[test]
+    fn bellman_ford_reports_negative_cycle_when_costs_overflow() {
+        // going around the cycle once costs -200, which does not fit in i8
+        let graph = Graph {
+            nodes: vec![0, 1],
+            edges: vec![vec![(1, -100i8)], vec![(0, -100)]],
+        };
+        for relaxation in RELAXATIONS {
+            let cycle = bellman_ford_single_source_in_weighted_indexed_graph(&graph, 0, relaxation)
+                .unwrap_err();
+            let mut nodes = assert_negative_cycle(&graph, &cycle);
+            nodes.sort();
+            assert_eq!(nodes, [0, 1]);
+        }
+
+        // without a cycle the cheapest path just does not fit in i8
+        let graph = Graph {
+            nodes: vec![0, 1, 2],
+            edges: vec![vec![(1, -100i8)], vec![(2, -100)], vec![]],
+        };
+        for relaxation in RELAXATIONS {
+            assert_eq!(
+                bellman_ford_single_source_in_weighted_indexed_graph(&graph, 0, relaxation),
+                Err(NegativeWeightsError::Overflow { node: 2 })
+            );
+            // too expensive paths are still skipped
+            let graph = Graph {
+                nodes: vec![0, 1, 2],
+                edges: vec![vec![(1, 100i8)], vec![(2, 100)], vec![]],
+            };
+            let tree = bellman_ford_single_source_in_weighted_indexed_graph(&graph, 0, relaxation)
+                .unwrap();
+            assert_eq!(tree.distances, [Some(0), Some(100), None]);
+        }
+    }
+
+    #[test]
+    fn bellman_ford_equals_dijkstra_for_non_negative_weights() {
+        let graph = make_test_weighted_indexed_graph();
+        for relaxation in RELAXATIONS {
+            for (start, target) in [(0, 5), (1, 5), (0, 7), (6, 3)] {
+                assert_eq!(
+                    bellman_ford_shortest_path_in_weighted_indexed_graph(
+                        &graph, start, target, relaxation
+                    )
+                    .unwrap()
+                    .map(|path| path.cost),
+                    dijkstra_shortest_path_in_weighted_indexed_graph(&graph, start, target)
+                        .map(|path| path.cost)
+                );
+            }
+        }
+
+        let mut rng = XorShift64::new(37);
+        for _ in 0..50 {
+            let graph = make_random_weighted_indexed_graph(&mut rng, 30, 80, 20);
+            let start = rng.index(30);
+            let dijkstra = dijkstra_single_source_in_weighted_indexed_graph(&graph, start);
+            for relaxation in RELAXATIONS {
+                let tree =
+                    bellman_ford_single_source_in_weighted_indexed_graph(&graph, start, relaxation)
+                        .unwrap();
+                assert_eq!(tree.distances, dijkstra.distances);
+            }
+        }
+    }
+
+    #[test]
+    fn bellman_ford_relaxations_agree_on_random_signed_graphs() {
+        let mut rng = XorShift64::new(38);
+        let mut cycles_found = 0;
+        for _ in 0..200 {
+            let n = 2 + rng.index(12);
+            let graph = Graph {
+                nodes: (0..n).collect(),
+                edges: (0..n)
+                    .map(|_| {
+                        (0..rng.index(4))
+                            .map(|_| (rng.index(n), rng.below(30) as i64 - 6))
+                            .collect()
+                    })
+                    .collect(),
+            };
+            let rounds =
+                bellman_ford_single_source_in_weighted_indexed_graph(&graph, 0, Relaxation::Rounds);
+            let queue =
+                bellman_ford_single_source_in_weighted_indexed_graph(&graph, 0, Relaxation::Queue);
+            match (rounds, queue) {
+                (Ok(rounds), Ok(queue)) => {
+                    assert_eq!(rounds.distances, queue.distances);
+                    // every distance is consistent with path found
+                    for target in 0..n {
+                        if let Some(path) = rounds.path(target) {
+                            assert_eq!(path.nodes.first(), Some(&0));
+                            assert_eq!(path.nodes.last(), Some(&target));
+                        }
+                    }
+                }
+                (Err(rounds), Err(queue)) => {
+                    assert_negative_cycle(&graph, &rounds);
+                    assert_negative_cycle(&graph, &queue);
+                    cycles_found += 1;
+                }
+                (rounds, queue) => panic!("{rounds:?} != {queue:?}"),
+            }
+        }
+        assert!(cycles_found > 0);
+    }
+}
@@ -0,0 +1,246 @@
+//! Benchmarking of graph searches on different graph layouts (used by `graphbench` binary).
+
+use crate::bench::{format_rows, percentile, Column, Format};
+use crate::data::csr_graph::CsrGraph;
+use crate::data::graph::{Graph, GraphNodeIndex};
+use crate::data::visit::Neighbors;
+use crate::rng::XorShift64;
+use crate::search::{bfs::bfs_shortest_path, dijkstra::dijkstra_shortest_path};
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+/// Memory layout of graph.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Layout {
+    /// Graph: vec of neighbours for every node.
+    Adjacency,
+    /// CsrGraph: flat vecs of all edges.
+    Csr,
+}
+
+impl Layout {
+    pub const ALL: [Layout; 2] = [Layout::Adjacency, Layout::Csr];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Layout::Adjacency => "adjacency",
+            Layout::Csr => "csr",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Layout> {
+        Layout::ALL.into_iter().find(|l| l.name() == name)
+    }
+}
+
+/// Benchmarked search.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Algorithm {
+    Bfs,
+    Dijkstra,
+}
+
+impl Algorithm {
+    pub const ALL: [Algorithm; 2] = [Algorithm::Bfs, Algorithm::Dijkstra];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Algorithm::Bfs => "bfs",
+            Algorithm::Dijkstra => "dijkstra",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Algorithm> {
+        Algorithm::ALL.into_iter().find(|a| a.name() == name)
+    }
+}
+
+/// Benchmark configuration.
+#[derive(Clone, Debug)]
+pub struct GraphBenchConfig {
+    /// Numbers of nodes (at least 2: start node and not reachable target).
+    pub sizes: Vec<usize>,
+    /// Number of edges going out of every node.
+    pub degree: usize,
+    pub algorithms: Vec<Algorithm>,
+    pub layouts: Vec<Layout>,
+    /// Not measured runs before the measured ones.
+    pub warmups: usize,
+    /// Measured runs (at least 1).
+    pub runs: usize,
+    pub seed: u64,
+}
+
+impl Default for GraphBenchConfig {
+    fn default() -> Self {
+        GraphBenchConfig {
+            sizes: vec![10_000, 100_000],
+            degree: 8,
+            algorithms: Algorithm::ALL.to_vec(),
+            layouts: Layout::ALL.to_vec(),
+            warmups: 1,
+            runs: 5,
+            seed: 42,
+        }
+    }
+}
+
+/// Result of benchmarking one search on one graph layout.
+#[derive(Clone, Debug)]
+pub struct GraphBenchResult {
+    pub algorithm: Algorithm,
+    pub layout: Layout,
+    pub nodes: usize,
+    pub edges: usize,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl GraphBenchResult {
+    /// Throughput of search: millions of edges scanned per second (by median run).
+    pub fn million_edges_per_second(&self) -> f64 {
+        self.edges as f64 / self.median.as_secs_f64().max(f64::MIN_POSITIVE) / 1e6
+    }
+}
+
+/// Random directed graph with 'nodes_count' nodes, where every node except the last one
+/// has 'degree' edges with weights in 1..=100 to random nodes (except the last one).
+/// The last node is not reachable, so search for it scans all edges reachable from start.
+pub fn make_bench_graph(nodes_count: usize, degree: usize, rng: &mut XorShift64) -> Graph<(), u32> {
+    let targets_count = nodes_count.saturating_sub(1).max(1);
+    let mut edges: Vec<Vec<(GraphNodeIndex, u32)>> = (0..nodes_count)
+        .map(|_| {
+            (0..degree)
+                .map(|_| (rng.index(targets_count), 1 + rng.below(100) as u32))
+                .collect()
+        })
+        .collect();
+    if let Some(last) = edges.last_mut() {
+        last.clear();
+    }
+    Graph {
+        nodes: vec![(); nodes_count],
+        edges,
+    }
+}
+
+/// Run all (selected) searches on all (selected) layouts of random graphs of all sizes.
+/// Every layout gets exactly the same graph for given size.
+pub fn run(config: &GraphBenchConfig) -> Vec<GraphBenchResult> {
+    assert!(
+        config.sizes.iter().all(|&size| size >= 2),
+        "sizes must be at least 2"
+    );
+    assert!(config.runs > 0, "at least 1 measured run is needed");
+    let mut results = Vec::new();
+    for &size in &config.sizes {
+        let mut rng = XorShift64::new(config.seed);
+        let graph = make_bench_graph(size, config.degree, &mut rng);
+        let csr = CsrGraph::from_graph(&graph);
+        let edges = csr.edges_count();
+        let target = graph.nodes.len() - 1;
+
+        for &algorithm in &config.algorithms {
+            for &layout in &config.layouts {
+                let (median, p95) = match layout {
+                    Layout::Adjacency => bench_one(&graph, target, algorithm, config),
+                    Layout::Csr => bench_one(&csr, target, algorithm, config),
+                };
+                results.push(GraphBenchResult {
+                    algorithm,
+                    layout,
+                    nodes: graph.nodes.len(),
+                    edges,
+                    median,
+                    p95,
+                });
+            }
+        }
+    }
+    results
+}
+
+/// Median and p95 time of search from the first node to (not reachable) target.
+fn bench_one<G>(
+    graph: &G,
+    target: GraphNodeIndex,
+    algorithm: Algorithm,
+    config: &GraphBenchConfig,
+) -> (Duration, Duration)
+where
+    G: Neighbors<NodeId = GraphNodeIndex, Edge = u32>,
+{
+    let search = || match algorithm {
+        Algorithm::Bfs => black_box(bfs_shortest_path(graph, 0, target)).is_none(),
+        Algorithm::Dijkstra => black_box(dijkstra_shortest_path(graph, 0, target)).is_none(),
+    };
+
+    for _ in 0..config.warmups {
+        search();
+    }
+    let mut times = Vec::with_capacity(config.runs);
+    for _ in 0..config.runs {
+        let start = Instant::now();
+        let not_found = search();
+        times.push(start.elapsed());
+        debug_assert!(not_found, "last node is not reachable");
+    }
+    times.sort();
+    (percentile(&times, 50), percentile(&times, 95))
+}
+
+/// Columns of results, in order of values returned by `row`.
+const COLUMNS: [Column; 7] = [
+    Column::text("algorithm", 10),
+    Column::text("layout", 10),
+    Column::number("nodes", 10),
+    Column::number("edges", 12),
+    Column::number("median_ns", 14),
+    Column::number("p95_ns", 14),
+    Column::number("medges_per_s", 14),
+];
+
+fn row(r: &GraphBenchResult) -> Vec<String> {
+    vec![
+        r.algorithm.name().to_string(),
+        r.layout.name().to_string(),
+        r.nodes.to_string(),
+        r.edges.to_string(),
+        r.median.as_nanos().to_string(),
+        r.p95.as_nanos().to_string(),
+        format!("{:.1}", r.million_edges_per_second()),
+    ]
+}
+
+pub fn format_results(results: &[GraphBenchResult], format: Format) -> String {
+    let rows: Vec<Vec<String>> = results.iter().map(row).collect();
+    format_rows(&COLUMNS, &rows, format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_and_format_works() {
+        let config = GraphBenchConfig {
+            sizes: vec![50, 200],
+            degree: 3,
+            warmups: 0,
+            runs: 3,
+            ..GraphBenchConfig::default()
+        };
+        let results = run(&config);
+        assert_eq!(results.len(), 2 * 2 * 2);
+        assert!(results.iter().all(|r| r.median <= r.p95));
+        assert_eq!(results[0].edges, 49 * 3);
+
+        let csv = format_results(&results, Format::Csv);
+        assert_eq!(csv.lines().count(), 1 + results.len());
+        assert!(csv.contains("dijkstra,csr,200,597,"));
+        let json = format_results(&results, Format::Json);
+        assert!(json.starts_with('[') && json.contains("\"layout\": \"adjacency\""));
+    }
+}
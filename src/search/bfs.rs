@@ -1,96 +1,214 @@
-use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    rc::Rc,
-};
+use std::collections::VecDeque;
 
-use crate::data::graph::{Graph, GraphNode, GraphNodeIndex};
+use crate::data::graph::{Graph, GraphNodeIndex, LinkedNode};
+use crate::data::visit::{LinkedNodes, Neighbors, VisitMap};
+use crate::search::shortest_path::{reconstruct_path_in_map, ShortestPath, ShortestPathTree};
+
+/// Search for shortest path in unweighted graph of any representation (see data::visit)
+/// by using Breadth First Search.
+/// Cost of path is number of its edges, None if target is not reachable.
+pub fn bfs_shortest_path<G: Neighbors>(
+    graph: &G,
+    start: G::NodeId,
+    target: G::NodeId,
+) -> Option<ShortestPath<G::NodeId, usize>> {
+    let (distances, previous) = bfs_tree(graph, start, Some(&target));
+    let cost = *distances.get(&target)?;
+    Some(ShortestPath {
+        nodes: reconstruct_path_in_map(&previous, target),
+        cost,
+    })
+}
 
 /// Search for shortest path in unweighted graph by using Breadth First Search.
+/// Cost of path is number of its edges, None if target is not reachable.
 pub fn bfs_shortest_path_in_indexed_graph<N, E>(
     graph: &Graph<N, E>,
     start: GraphNodeIndex,
     target: GraphNodeIndex,
-) -> Vec<GraphNodeIndex> {
-    let mut path = Vec::new();
+) -> Option<ShortestPath<GraphNodeIndex, usize>> {
+    bfs_shortest_path(graph, start, target)
+}
+
+/// Shortest paths from start to all nodes in unweighted graph, by using Breadth First Search.
+pub fn bfs_single_source_in_indexed_graph<N, E>(
+    graph: &Graph<N, E>,
+    start: GraphNodeIndex,
+) -> ShortestPathTree<usize> {
+    let (distances, previous) = bfs_tree(graph, start, None);
+    ShortestPathTree {
+        start,
+        distances,
+        previous,
+    }
+}
 
-    // bfs traverse until we reach target elemnet
+/// BFS traverse until we reach target element (or all reachable elements if there is no target).
+/// Returns distances (number of edges) and predecessors of reached nodes.
+fn bfs_tree<G: Neighbors>(
+    graph: &G,
+    start: G::NodeId,
+    target: Option<&G::NodeId>,
+) -> (G::Map<usize>, G::Map<G::NodeId>) {
     let mut fifo = VecDeque::new();
-    let mut seen = vec![false; graph.nodes.len()];
-    let mut prev = vec![None; graph.nodes.len()];
+    let mut distances: G::Map<usize> = graph.visit_map();
+    let mut previous: G::Map<G::NodeId> = graph.visit_map();
+    if !graph.contains_node(&start) {
+        return (distances, previous);
+    }
 
+    distances.insert(start.clone(), 0);
     fifo.push_back(start);
-    seen[start] = true;
 
-    'w: while let Some(current) = fifo.pop_front() {
-        for &(neighbour, _) in &graph.edges[current] {
-            if !seen[neighbour] {
-                seen[neighbour] = true;
-                fifo.push_back(neighbour);
-                prev[neighbour] = Some(current);
-                if neighbour == target {
-                    break 'w;
+    while let Some(current) = fifo.pop_front() {
+        if target.is_some_and(|target| distances.contains(target)) {
+            break;
+        }
+        let current_distance = distances.get(&current).copied().unwrap_or_default();
+        graph.for_each_neighbour(&current, |neighbour, _| {
+            if !distances.contains(neighbour) {
+                distances.insert(neighbour.clone(), current_distance + 1);
+                previous.insert(neighbour.clone(), current.clone());
+                fifo.push_back(neighbour.clone());
+            }
+        });
+    }
+
+    (distances, previous)
+}
+
+/// Search for the cheapest path in graph with edge weights 0 or 1 (extracted from the edge by 'weight'),
+/// by using 0-1 BFS. Returns None if target is not reachable.
+pub fn zero_one_bfs_shortest_path_in_indexed_graph_by<N, E, F>(
+    graph: &Graph<N, E>,
+    start: GraphNodeIndex,
+    target: GraphNodeIndex,
+    weight: F,
+) -> Option<ShortestPath<GraphNodeIndex, usize>>
+where
+    F: Fn(&E) -> usize,
+{
+    zero_one_bfs_single_source_in_indexed_graph_by(graph, start, weight).path(target)
+}
+
+/// The cheapest paths from start to all nodes in graph with edge weights 0 or 1, by using 0-1 BFS:
+/// like Dijkstra, but in O(V + E), because deque with 0-edges pushed to the front
+/// and 1-edges pushed to the back is always sorted by distance (with at most two different distances).
+/// Panics if 'weight' returns anything else than 0 or 1.
+pub fn zero_one_bfs_single_source_in_indexed_graph_by<N, E, F>(
+    graph: &Graph<N, E>,
+    start: GraphNodeIndex,
+    weight: F,
+) -> ShortestPathTree<usize>
+where
+    F: Fn(&E) -> usize,
+{
+    let mut deque = VecDeque::new();
+    let mut distances: Vec<Option<usize>> = vec![None; graph.nodes.len()];
+    let mut previous = vec![None; graph.nodes.len()];
+    let mut done = vec![false; graph.nodes.len()];
+
+    deque.push_back(start);
+    distances[start] = Some(0);
+
+    while let Some(current) = deque.pop_front() {
+        // node can be in deque twice (first with 1-edge, then with 0-edge)
+        if done[current] {
+            continue;
+        }
+        done[current] = true;
+        let current_distance = distances[current].unwrap_or_default();
+        for (neighbour, edge) in &graph.edges[current] {
+            let edge_weight = weight(edge);
+            assert!(
+                edge_weight <= 1,
+                "0-1 BFS edge weight must be 0 or 1, got {edge_weight}"
+            );
+            let neighbour_distance = current_distance + edge_weight;
+            if distances[*neighbour].is_none_or(|distance| neighbour_distance < distance) {
+                distances[*neighbour] = Some(neighbour_distance);
+                previous[*neighbour] = Some(current);
+                if edge_weight == 0 {
+                    deque.push_front(*neighbour);
+                } else {
+                    deque.push_back(*neighbour);
                 }
             }
         }
     }
 
-    let mut current = target;
-    path.push(current);
-    while let Some(parent) = prev[current] {
-        path.push(parent);
-        current = parent;
+    ShortestPathTree {
+        start,
+        distances,
+        previous,
     }
-    path.reverse();
-
-    path
 }
 
-pub fn bfs_shortest_path_in_linked_graph<V, E>(
-    start: Rc<GraphNode<V, E>>,
-    target: Rc<GraphNode<V, E>>,
-) -> Vec<Rc<GraphNode<V, E>>> {
-    let mut path = Vec::new();
+/// The nearest source of node and distance (number of edges) to it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NearestSource {
+    pub source: GraphNodeIndex,
+    pub distance: usize,
+}
 
-    // bfs traverse until we reach target elemnet
-    let mut previous = HashMap::new();
-    let mut seen = HashSet::new();
+/// Nearest source for every node in unweighted graph, by BFS started from all sources at once.
+/// Index of returned vec is GraphNodeIndex, None if node is not reachable from any source.
+/// When more sources are at the same distance, the first one in 'sources' wins.
+pub fn multi_source_bfs_in_indexed_graph<N, E>(
+    graph: &Graph<N, E>,
+    sources: &[GraphNodeIndex],
+) -> Vec<Option<NearestSource>> {
     let mut fifo = VecDeque::new();
+    let mut nearest: Vec<Option<NearestSource>> = vec![None; graph.nodes.len()];
 
-    fifo.push_back(start.clone());
-    seen.insert(start.clone());
-    previous.insert(start.clone(), None);
+    for &source in sources {
+        if nearest[source].is_none() {
+            nearest[source] = Some(NearestSource {
+                source,
+                distance: 0,
+            });
+            fifo.push_back(source);
+        }
+    }
 
-    'w: while let Some(current) = fifo.pop_front() {
-        for (neighbour, _) in current.neighbours.borrow().iter() {
-            if !seen.contains(neighbour) {
-                seen.insert(neighbour.clone());
-                fifo.push_back(neighbour.clone());
-                previous.insert(neighbour.clone(), Some(current.clone()));
-                if neighbour.clone() == target {
-                    break 'w;
-                }
+    while let Some(current) = fifo.pop_front() {
+        let Some(NearestSource { source, distance }) = nearest[current] else {
+            continue;
+        };
+        for &(neighbour, _) in &graph.edges[current] {
+            if nearest[neighbour].is_none() {
+                nearest[neighbour] = Some(NearestSource {
+                    source,
+                    distance: distance + 1,
+                });
+                fifo.push_back(neighbour);
             }
         }
     }
 
-    path.push(target.clone());
-    let mut current = target.clone();
-    while let Some(parrent) = &previous[&current] {
-        path.push(parrent.clone());
-        current = parrent.clone();
-    }
-    path.reverse();
+    nearest
+}
 
-    path
+/// Search for shortest path in unweighted linked graph by using Breadth First Search.
+/// Cost of path is number of its edges, None if target is not reachable.
+pub fn bfs_shortest_path_in_linked_graph<N: LinkedNode>(
+    start: N,
+    target: N,
+) -> Option<ShortestPath<N, usize>> {
+    bfs_shortest_path(&LinkedNodes::new(), start, target)
 }
 
 #[cfg(test)]
 mod tests {
     use crate::data::graph::{
         make_test_unweighted_indexed_graph, make_test_unweighted_linked_graph_nodes,
+        make_test_weighted_indexed_graph,
     };
+    use crate::search::dijkstra::dijkstra_single_source_in_indexed_graph_by;
 
     use super::*;
+    use crate::data::graph::GraphNode;
+    use std::{collections::HashSet, rc::Rc};
 
     ///////////--- Test graph --- /////////
     //                 //                //
@@ -111,19 +229,113 @@ mod tests {
         // GraphNodeIndex:  0,   1,   2,   3,   4,   5,   6,   7
         //                 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'
 
-        let shortest_path = bfs_shortest_path_in_indexed_graph(&graph, 0, 5);
-        assert_eq!(shortest_path, [0, 4, 5]);
-        let shortest_path = bfs_shortest_path_in_indexed_graph(&graph, 1, 5);
-        assert_eq!(shortest_path, [1, 6, 5]);
-        let shortest_path = bfs_shortest_path_in_indexed_graph(&graph, 0, 7);
-        assert_eq!(shortest_path, [0, 3, 7]);
+        let shortest_path = bfs_shortest_path_in_indexed_graph(&graph, 0, 5).unwrap();
+        assert_eq!(shortest_path.nodes, [0, 4, 5]);
+        assert_eq!(shortest_path.cost, 2);
+        let shortest_path = bfs_shortest_path_in_indexed_graph(&graph, 1, 5).unwrap();
+        assert_eq!(shortest_path.nodes, [1, 6, 5]);
+        let shortest_path = bfs_shortest_path_in_indexed_graph(&graph, 0, 7).unwrap();
+        assert_eq!(shortest_path.nodes, [0, 3, 7]);
+        let shortest_path = bfs_shortest_path_in_indexed_graph(&graph, 3, 3).unwrap();
+        assert_eq!(shortest_path.nodes, [3]);
+        assert_eq!(shortest_path.cost, 0);
+    }
+
+    #[test]
+    fn bfs_reports_unreachable_target() {
+        let mut graph = make_test_unweighted_indexed_graph();
+        // disconnected node
+        graph.nodes.push('i');
+        graph.edges.push(Vec::new());
+        assert_eq!(bfs_shortest_path_in_indexed_graph(&graph, 0, 8), None);
+
+        let tree = bfs_single_source_in_indexed_graph(&graph, 0);
+        assert_eq!(
+            tree.distances,
+            [
+                Some(0),
+                Some(1),
+                Some(1),
+                Some(1),
+                Some(1),
+                Some(2),
+                Some(2),
+                Some(2),
+                None
+            ]
+        );
+        assert_eq!(tree.path(8), None);
+        assert_eq!(tree.path(5).unwrap().nodes, [0, 4, 5]);
+        assert_eq!(tree.previous[0], None);
+    }
+
+    #[test]
+    fn zero_one_bfs_works() {
+        let graph = make_test_weighted_indexed_graph();
+        // edges of weight 1 are free, heavier ones cost 1
+        let weight = |&w: &u64| usize::from(w > 1);
+        let path = zero_one_bfs_shortest_path_in_indexed_graph_by(&graph, 0, 7, weight).unwrap();
+        assert_eq!(path.cost, 1);
+        assert_eq!(path.nodes.first(), Some(&0));
+        assert_eq!(path.nodes.last(), Some(&7));
+
+        // the same as Dijkstra with these weights
+        for start in 0..8 {
+            let tree = zero_one_bfs_single_source_in_indexed_graph_by(&graph, start, weight);
+            let dijkstra = dijkstra_single_source_in_indexed_graph_by(&graph, start, |w| weight(w));
+            assert_eq!(tree.distances, dijkstra.distances);
+        }
+
+        // with all edges weight 1 it is BFS
+        let graph = make_test_unweighted_indexed_graph();
+        assert_eq!(
+            zero_one_bfs_single_source_in_indexed_graph_by(&graph, 1, |_| 1).distances,
+            bfs_single_source_in_indexed_graph(&graph, 1).distances
+        );
+    }
+
+    #[test]
+    fn multi_source_bfs_works() {
+        let graph = make_test_unweighted_indexed_graph();
+        let nearest = multi_source_bfs_in_indexed_graph(&graph, &[6, 3]);
+        let near = |source, distance| Some(NearestSource { source, distance });
+        assert_eq!(
+            nearest,
+            [
+                near(3, 1),
+                near(6, 1),
+                near(3, 1),
+                near(3, 0),
+                near(3, 1),
+                near(6, 1),
+                near(6, 0),
+                near(3, 1)
+            ]
+        );
+
+        // the same as the minimum over single source BFS
+        let sources = [7, 2, 5];
+        let nearest = multi_source_bfs_in_indexed_graph(&graph, &sources);
+        for (node, nearest) in nearest.iter().enumerate() {
+            let distance = sources
+                .iter()
+                .filter_map(|&source| {
+                    bfs_single_source_in_indexed_graph(&graph, source).distances[node]
+                })
+                .min();
+            assert_eq!(nearest.map(|n| n.distance), distance);
+        }
+
+        assert_eq!(multi_source_bfs_in_indexed_graph(&graph, &[]), [None; 8]);
     }
 
     #[test] //bfs_shortest_path_in_linked_graph
     fn bfs_shortest_path_works_for_linked_graph() {
-        let (start, end) = make_test_unweighted_linked_graph_nodes('a', Some('g'));
+        let (_graph, start, end) = make_test_unweighted_linked_graph_nodes('a', Some('g'));
         let end = end.unwrap();
-        let path = bfs_shortest_path_in_linked_graph(start.clone(), end.clone());
+        let path = bfs_shortest_path_in_linked_graph(start.clone(), end.clone()).unwrap();
+        assert_eq!(path.cost, 2);
+        let path = path.nodes;
 
         let unique: HashSet<Rc<GraphNode<char, ()>>> = path.iter().cloned().collect();
 
@@ -0,0 +1,295 @@
+use crate::data::graph::{Graph, GraphNodeIndex};
+use crate::data::weight::Weight;
+use crate::search::dijkstra::ShortestDistance;
+use crate::search::shortest_path::ShortestPath;
+use std::collections::{BinaryHeap, VecDeque};
+
+/// Path through 'meeting' node: start -> ... -> meeting (by forward parents) -> ... -> target (by backward parents).
+fn join_paths(
+    meeting: GraphNodeIndex,
+    forward_previous: &[Option<GraphNodeIndex>],
+    backward_previous: &[Option<GraphNodeIndex>],
+) -> Vec<GraphNodeIndex> {
+    let mut path = vec![meeting];
+    let mut current = meeting;
+    while let Some(parent) = forward_previous[current] {
+        path.push(parent);
+        current = parent;
+    }
+    path.reverse();
+
+    let mut current = meeting;
+    while let Some(next) = backward_previous[current] {
+        path.push(next);
+        current = next;
+    }
+    path
+}
+
+/// Search for shortest path in unweighted graph by using Breadth First Search from both sides:
+/// forward from start and backward (over reversed edges) from target.
+/// Each step expands the whole level of the smaller frontier, and search stops after the first level
+/// which meets the other side (the best meeting point of that level gives the shortest path).
+///
+/// Returns None when target is not reachable from start.
+pub fn bidirectional_bfs_shortest_path_in_indexed_graph<V, E: Clone>(
+    graph: &Graph<V, E>,
+    start: GraphNodeIndex,
+    target: GraphNodeIndex,
+) -> Option<ShortestPath<GraphNodeIndex, usize>> {
+    let reversed_edges = graph.reversed_edges();
+    let n = graph.nodes.len();
+
+    // [forward, backward]
+    let mut distances = [vec![None; n], vec![None; n]];
+    let mut previous = [vec![None; n], vec![None; n]];
+    let mut frontiers = [VecDeque::from([start]), VecDeque::from([target])];
+    distances[0][start] = Some(0);
+    distances[1][target] = Some(0);
+    if start == target {
+        return Some(ShortestPath {
+            nodes: vec![start],
+            cost: 0,
+        });
+    }
+
+    while !frontiers[0].is_empty() && !frontiers[1].is_empty() {
+        let side = if frontiers[0].len() <= frontiers[1].len() {
+            0
+        } else {
+            1
+        };
+        let other = 1 - side;
+        let edges = if side == 0 {
+            &graph.edges
+        } else {
+            &reversed_edges
+        };
+
+        // (total distance, meeting node) - the best one found in this level
+        let mut best: Option<(usize, GraphNodeIndex)> = None;
+        for _ in 0..frontiers[side].len() {
+            let current = frontiers[side].pop_front().unwrap();
+            let current_distance = distances[side][current].unwrap();
+            for &(neighbour, _) in &edges[current] {
+                if distances[side][neighbour].is_none() {
+                    distances[side][neighbour] = Some(current_distance + 1);
+                    previous[side][neighbour] = Some(current);
+                    frontiers[side].push_back(neighbour);
+                }
+                if let Some(other_distance) = distances[other][neighbour] {
+                    let total = current_distance + 1 + other_distance;
+                    if best.is_none_or(|(best_total, _)| total < best_total) {
+                        best = Some((total, neighbour));
+                    }
+                }
+            }
+        }
+
+        if let Some((cost, meeting)) = best {
+            // meeting node could be reached by this side before, but then its distance is not bigger
+            return Some(ShortestPath {
+                nodes: join_paths(meeting, &previous[0], &previous[1]),
+                cost,
+            });
+        }
+    }
+
+    None
+}
+
+/// Search for the cheapest path by using Dijkstra from both sides:
+/// forward from start and backward (over reversed edges) from target, alternating the sides.
+/// Stops when the sum of the smallest distances in both heaps is not less than the best path found,
+/// so typically it settles much less nodes than unidirectional Dijkstra.
+///
+/// Returns None when target is not reachable from start.
+/// Paths which cost does not fit in W (overflow) are not taken into account.
+pub fn bidirectional_dijkstra_shortest_path_in_weighted_indexed_graph<V, W: Weight>(
+    graph: &Graph<V, W>,
+    start: GraphNodeIndex,
+    target: GraphNodeIndex,
+) -> Option<ShortestPath<GraphNodeIndex, W>> {
+    let reversed_edges = graph.reversed_edges();
+    let n = graph.nodes.len();
+
+    // [forward, backward], None distance means infinity
+    let mut distances: [Vec<Option<W>>; 2] = [vec![None; n], vec![None; n]];
+    let mut previous = [vec![None; n], vec![None; n]];
+    let mut settled = [vec![false; n], vec![false; n]];
+    let mut heaps = [BinaryHeap::new(), BinaryHeap::new()];
+
+    distances[0][start] = Some(W::zero());
+    distances[1][target] = Some(W::zero());
+    heaps[0].push(ShortestDistance {
+        index: start,
+        value: W::zero(),
+    });
+    heaps[1].push(ShortestDistance {
+        index: target,
+        value: W::zero(),
+    });
+
+    // the cheapest path found so far: (cost, meeting node)
+    let mut best: Option<(W, GraphNodeIndex)> = (start == target).then(|| (W::zero(), start));
+
+    while let (Some(forward_top), Some(backward_top)) = (heaps[0].peek(), heaps[1].peek()) {
+        // every path not found yet is at least as expensive as the sum of both tops (overflow means even more)
+        if let Some((cost, _)) = &best {
+            if forward_top
+                .value
+                .checked_add(&backward_top.value)
+                .is_none_or(|sum| sum >= *cost)
+            {
+                break;
+            }
+        }
+
+        let side = if forward_top.value <= backward_top.value {
+            0
+        } else {
+            1
+        };
+        let other = 1 - side;
+        let edges = if side == 0 {
+            &graph.edges
+        } else {
+            &reversed_edges
+        };
+
+        let ShortestDistance {
+            index: current,
+            value: current_distance,
+        } = heaps[side].pop().unwrap();
+        if settled[side][current] {
+            continue; // outdated heap entry
+        }
+        settled[side][current] = true;
+
+        for (neighbour, edge) in &edges[current] {
+            let neighbour = *neighbour;
+            let Some(neighbour_distance) = current_distance.checked_add(edge) else {
+                continue; // overflow
+            };
+            if distances[side][neighbour]
+                .as_ref()
+                .is_none_or(|distance| neighbour_distance < *distance)
+            {
+                distances[side][neighbour] = Some(neighbour_distance.clone());
+                previous[side][neighbour] = Some(current);
+                heaps[side].push(ShortestDistance {
+                    index: neighbour,
+                    value: neighbour_distance,
+                });
+            }
+            if let (Some(this_distance), Some(other_distance)) =
+                (&distances[side][neighbour], &distances[other][neighbour])
+            {
+                if let Some(total) = this_distance.checked_add(other_distance) {
+                    if best.as_ref().is_none_or(|(cost, _)| total < *cost) {
+                        best = Some((total, neighbour));
+                    }
+                }
+            }
+        }
+    }
+
+    best.map(|(cost, meeting)| ShortestPath {
+        nodes: join_paths(meeting, &previous[0], &previous[1]),
+        cost,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::graph::{
+        make_random_weighted_indexed_graph, make_test_unweighted_indexed_graph,
+        make_test_weighted_indexed_graph,
+    };
+    use crate::rng::XorShift64;
+    use crate::search::{
+        bfs::bfs_shortest_path_in_indexed_graph,
+        dijkstra::dijkstra_shortest_path_in_weighted_indexed_graph,
+    };
+
+    /// Cost of path (the cheapest edge between neighbouring nodes), panics if path does not follow edges.
+    fn path_cost(graph: &Graph<usize, u64>, path: &[GraphNodeIndex]) -> u64 {
+        path.windows(2)
+            .map(|w| {
+                graph.edges[w[0]]
+                    .iter()
+                    .filter(|&&(n, _)| n == w[1])
+                    .map(|&(_, e)| e)
+                    .min()
+                    .expect("incorrect path")
+            })
+            .sum()
+    }
+
+    #[test]
+    fn bidirectional_search_works_for_test_graphs() {
+        let graph = make_test_unweighted_indexed_graph();
+        let path = bidirectional_bfs_shortest_path_in_indexed_graph(&graph, 0, 5).unwrap();
+        assert_eq!((path.nodes.len(), path.cost), (3, 2));
+        assert_eq!(
+            bidirectional_bfs_shortest_path_in_indexed_graph(&graph, 2, 2),
+            Some(ShortestPath {
+                nodes: vec![2],
+                cost: 0
+            })
+        );
+
+        let graph = make_test_weighted_indexed_graph();
+        assert_eq!(
+            bidirectional_dijkstra_shortest_path_in_weighted_indexed_graph(&graph, 1, 5),
+            Some(ShortestPath {
+                nodes: vec![1, 0, 4, 5],
+                cost: 3
+            })
+        );
+        assert_eq!(
+            bidirectional_dijkstra_shortest_path_in_weighted_indexed_graph(&graph, 0, 7),
+            Some(ShortestPath {
+                nodes: vec![0, 3, 7],
+                cost: 3
+            })
+        );
+    }
+
+    #[test]
+    fn bidirectional_search_matches_unidirectional_on_random_graphs() {
+        let mut rng = XorShift64::new(34);
+        for _ in 0..200 {
+            let nodes_count = 1 + rng.index(30);
+            let edges_count = rng.index(3 * nodes_count);
+            let graph = make_random_weighted_indexed_graph(&mut rng, nodes_count, edges_count, 10);
+            let start = rng.index(nodes_count);
+            let target = rng.index(nodes_count);
+
+            let bfs_path = bfs_shortest_path_in_indexed_graph(&graph, start, target);
+            let path = bidirectional_bfs_shortest_path_in_indexed_graph(&graph, start, target);
+            assert_eq!(path.is_some(), bfs_path.is_some());
+            if let (Some(path), Some(bfs_path)) = (path, bfs_path) {
+                assert_eq!(path.cost, bfs_path.cost);
+                assert_eq!(path.nodes.len(), path.cost + 1);
+                assert_eq!(path.nodes[0], start);
+                assert_eq!(path.nodes[path.cost], target);
+                path_cost(&graph, &path.nodes);
+            }
+
+            let dijkstra_path =
+                dijkstra_shortest_path_in_weighted_indexed_graph(&graph, start, target);
+            let path = bidirectional_dijkstra_shortest_path_in_weighted_indexed_graph(
+                &graph, start, target,
+            );
+            assert_eq!(path.is_some(), dijkstra_path.is_some());
+            if let (Some(path), Some(dijkstra_path)) = (path, dijkstra_path) {
+                assert_eq!(path.cost, dijkstra_path.cost);
+                assert_eq!(path.cost, path_cost(&graph, &path.nodes));
+                assert_eq!(path.nodes[0], start);
+                assert_eq!(path.nodes[path.nodes.len() - 1], target);
+            }
+        }
+    }
+}
@@ -1,6 +1,17 @@
+use std::{cmp::Ordering, ops::Range};
+
 /// Binary search for element in slice.
 /// If not found returns Err(insertion_index), where 'insertion_index' is the index where element can be inserted so that collection is still sorted.
 pub fn binary_search<T: Ord>(sorted: &[T], el: &T) -> Result<usize, usize> {
+    binary_search_by(sorted, |m| m.cmp(el))
+}
+
+/// Binary search with comparator function.
+/// Comparator returns ordering of the slice element against the searched one, i.e. Less if element is on the left of searched one.
+pub fn binary_search_by<T, F>(sorted: &[T], mut compare: F) -> Result<usize, usize>
+where
+    F: FnMut(&T) -> Ordering,
+{
     // left, mid, right
     let mut left = 0;
     let mut size = sorted.len();
@@ -9,23 +20,110 @@ pub fn binary_search<T: Ord>(sorted: &[T], el: &T) -> Result<usize, usize> {
     while left < right {
         let mid = left + size / 2; // it will not overflow usize as well as it is in bound, cause size/2 < size and left + size <= len(), so left + size/2 < len()
                                    // note: size/2 < size, because left < right
-        let m = &sorted[mid];
-        // el is on left of m
-        if el < m {
-            right = mid
-        }
-        // el is on right of m
-        if m < el {
-            left = mid + 1
-        }
-        if m == el {
-            return Ok(mid);
+        match compare(&sorted[mid]) {
+            // el is on left of m
+            Ordering::Greater => right = mid,
+            // el is on right of m
+            Ordering::Less => left = mid + 1,
+            Ordering::Equal => return Ok(mid),
         }
         size = right - left
     }
     Err(left)
 }
 
+/// Binary search by key extracted from slice elements.
+pub fn binary_search_by_key<T, K, F>(sorted: &[T], key: &K, mut f: F) -> Result<usize, usize>
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    binary_search_by(sorted, |m| f(m).cmp(key))
+}
+
+/// Index of the first element for which predicate is false.
+/// Slice must be partitioned: [pred is true | pred is false].
+pub fn partition_point_by<T, P>(slice: &[T], mut pred: P) -> usize
+where
+    P: FnMut(&T) -> bool,
+{
+    // invariant: pred is true for ..left and false for right..
+    let mut left = 0;
+    let mut right = slice.len();
+    while left < right {
+        let mid = left + (right - left) / 2;
+        if pred(&slice[mid]) {
+            left = mid + 1;
+        } else {
+            right = mid;
+        }
+    }
+    left
+}
+
+/// Index of the first element which is not less than 'el' (first position of 'el' when it is present).
+pub fn lower_bound<T: Ord>(sorted: &[T], el: &T) -> usize {
+    lower_bound_by(sorted, |m| m.cmp(el))
+}
+
+pub fn lower_bound_by<T, F>(sorted: &[T], mut compare: F) -> usize
+where
+    F: FnMut(&T) -> Ordering,
+{
+    partition_point_by(sorted, |m| compare(m) == Ordering::Less)
+}
+
+pub fn lower_bound_by_key<T, K, F>(sorted: &[T], key: &K, mut f: F) -> usize
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    lower_bound_by(sorted, |m| f(m).cmp(key))
+}
+
+/// Index of the first element which is greater than 'el' (one past last position of 'el' when it is present).
+pub fn upper_bound<T: Ord>(sorted: &[T], el: &T) -> usize {
+    upper_bound_by(sorted, |m| m.cmp(el))
+}
+
+pub fn upper_bound_by<T, F>(sorted: &[T], mut compare: F) -> usize
+where
+    F: FnMut(&T) -> Ordering,
+{
+    partition_point_by(sorted, |m| compare(m) != Ordering::Greater)
+}
+
+pub fn upper_bound_by_key<T, K, F>(sorted: &[T], key: &K, mut f: F) -> usize
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    upper_bound_by(sorted, |m| f(m).cmp(key))
+}
+
+/// Range of all elements equal to 'el' (empty range at insertion index when not present).
+pub fn equal_range<T: Ord>(sorted: &[T], el: &T) -> Range<usize> {
+    equal_range_by(sorted, |m| m.cmp(el))
+}
+
+pub fn equal_range_by<T, F>(sorted: &[T], mut compare: F) -> Range<usize>
+where
+    F: FnMut(&T) -> Ordering,
+{
+    let start = lower_bound_by(sorted, &mut compare);
+    // upper bound is searched only on the right of lower bound
+    let end = start + upper_bound_by(&sorted[start..], compare);
+    start..end
+}
+
+pub fn equal_range_by_key<T, K, F>(sorted: &[T], key: &K, mut f: F) -> Range<usize>
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    equal_range_by(sorted, |m| f(m).cmp(key))
+}
+
 #[test]
 fn binary_search_works() {
     let a = [1, 2, 40, 61, 121, 333, 335, 380];
@@ -37,3 +135,48 @@ fn binary_search_works() {
     assert_eq!(Ok(6), binary_search(&a, &335));
     assert_eq!(Err(8), binary_search(&a, &400));
 }
+
+#[test]
+fn bounds_work_with_duplicates() {
+    let a = [1, 2, 2, 2, 5, 7, 7, 9];
+    assert_eq!(lower_bound(&a, &2), 1);
+    assert_eq!(upper_bound(&a, &2), 4);
+    assert_eq!(equal_range(&a, &7), 5..7);
+    assert_eq!(equal_range(&a, &6), 5..5);
+    assert_eq!(equal_range(&a, &0), 0..0);
+    assert_eq!(equal_range(&a, &10), 8..8);
+    assert_eq!(partition_point_by(&a, |&x| x < 5), 4);
+
+    let pairs = [(1, 'a'), (3, 'b'), (3, 'c'), (4, 'd')];
+    assert_eq!(equal_range_by_key(&pairs, &3, |&(k, _)| k), 1..3);
+    assert_eq!(lower_bound_by_key(&pairs, &4, |&(k, _)| k), 3);
+    assert_eq!(upper_bound_by_key(&pairs, &1, |&(k, _)| k), 1);
+    assert_eq!(binary_search_by_key(&pairs, &2, |&(k, _)| k), Err(1));
+}
+
+#[test]
+fn binary_search_family_matches_std() {
+    use crate::rng::XorShift64;
+
+    let mut rng = XorShift64::new(27);
+    for _ in 0..500 {
+        let len = rng.index(40);
+        // small values, so there are many duplicates
+        let mut a: Vec<u64> = (0..len).map(|_| rng.below(10)).collect();
+        a.sort();
+
+        for el in 0..=11 {
+            let lower = a.partition_point(|x| *x < el);
+            let upper = a.partition_point(|x| *x <= el);
+            assert_eq!(lower_bound(&a, &el), lower, "{a:?} {el}");
+            assert_eq!(upper_bound(&a, &el), upper, "{a:?} {el}");
+            assert_eq!(equal_range(&a, &el), lower..upper, "{a:?} {el}");
+            assert_eq!(partition_point_by(&a, |x| *x < el), lower, "{a:?} {el}");
+            match (binary_search(&a, &el), a.binary_search(&el)) {
+                (Ok(found), Ok(_)) => assert!((lower..upper).contains(&found)),
+                (Err(insertion), Err(std_insertion)) => assert_eq!(insertion, std_insertion),
+                (result, std_result) => panic!("{a:?} {el}: {result:?} != {std_result:?}"),
+            }
+        }
+    }
+}
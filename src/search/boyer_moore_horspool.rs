@@ -0,0 +1,81 @@
+use crate::search::haystack::{EmptyPatternMatches, Haystack};
+
+/// Iterator over all (also overlapping) match positions of pattern in haystack.
+pub struct HorspoolMatches<'a> {
+    haystack: &'a [u8],
+    pattern: &'a [u8],
+    // Some if pattern is empty
+    empty: Option<EmptyPatternMatches<'a>>,
+    // how far window can be shifted, depending on the haystack byte aligned with the last pattern byte
+    shift: [usize; 256],
+    // start of current window in haystack
+    position: usize,
+}
+
+/// Search for all positions (byte indexes) of pattern in haystack by using Boyer–Moore–Horspool.
+/// Compares window from the end and skips up to pattern.len() bytes at once, so it is sublinear on average.
+/// Works for both &str and &[u8]. Empty pattern matches at every char boundary of &str
+/// and at every position 0..=haystack.len() of &[u8].
+pub fn horspool_search<'a, H, P>(haystack: &'a H, pattern: &'a P) -> HorspoolMatches<'a>
+where
+    H: Haystack + ?Sized,
+    P: AsRef<[u8]> + ?Sized,
+{
+    let pattern = pattern.as_ref();
+    let m = pattern.len();
+
+    // bytes not present in pattern (except the last one) allow to skip whole pattern length
+    let mut shift = [m; 256];
+    for (i, &byte) in pattern.iter().enumerate().take(m.saturating_sub(1)) {
+        shift[byte as usize] = m - 1 - i;
+    }
+
+    HorspoolMatches {
+        haystack: haystack.as_ref(),
+        pattern,
+        empty: EmptyPatternMatches::of_pattern(haystack, pattern),
+        shift,
+        position: 0,
+    }
+}
+
+impl Iterator for HorspoolMatches<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if let Some(empty) = &mut self.empty {
+            return empty.next();
+        }
+        let m = self.pattern.len();
+
+        while self.position + m <= self.haystack.len() {
+            let start = self.position;
+            let window = &self.haystack[start..start + m];
+            // shift is always >= 1 and never skips a match, so it is also valid after a match
+            self.position += self.shift[window[m - 1] as usize];
+            if window.iter().rev().eq(self.pattern.iter().rev()) {
+                return Some(start);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::tests::test_pattern_search;
+
+    #[test]
+    fn horspool_search_works() {
+        assert_eq!(
+            horspool_search("abracadabra", "abra").collect::<Vec<_>>(),
+            [0, 7]
+        );
+        assert_eq!(
+            horspool_search(b"aaaa".as_slice(), b"aa").collect::<Vec<_>>(),
+            [0, 1, 2]
+        );
+        test_pattern_search(|haystack, pattern| horspool_search(haystack, pattern).collect());
+    }
+}
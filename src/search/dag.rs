@@ -0,0 +1,226 @@
+use crate::data::graph::{Graph, GraphNodeIndex};
+use crate::data::weight::Weight;
+use crate::search::shortest_path::{reconstruct_path, ShortestPath, ShortestPathTree};
+use std::collections::VecDeque;
+
+/// Topological order of nodes (every edge goes from earlier to later node) by Kahn's algorithm,
+/// None if graph has a cycle (i.e. it is not a DAG).
+pub fn topological_order<V, E>(graph: &Graph<V, E>) -> Option<Vec<GraphNodeIndex>> {
+    let mut in_degree = vec![0; graph.nodes.len()];
+    for neighbours in &graph.edges {
+        for &(neighbour, _) in neighbours {
+            in_degree[neighbour] += 1;
+        }
+    }
+
+    let mut fifo: VecDeque<GraphNodeIndex> = (0..graph.nodes.len())
+        .filter(|&node| in_degree[node] == 0)
+        .collect();
+    let mut order = Vec::with_capacity(graph.nodes.len());
+    while let Some(current) = fifo.pop_front() {
+        order.push(current);
+        for &(neighbour, _) in &graph.edges[current] {
+            in_degree[neighbour] -= 1;
+            if in_degree[neighbour] == 0 {
+                fifo.push_back(neighbour);
+            }
+        }
+    }
+
+    // nodes on cycles never get to zero in-degree
+    (order.len() == graph.nodes.len()).then_some(order)
+}
+
+/// The cheapest paths from start to all nodes in DAG where edges are weights (can be negative),
+/// by relaxing edges in topological order, in O(V + E).
+/// Returns None if graph has a cycle.
+pub fn dag_shortest_paths_in_weighted_indexed_graph<V, W: Weight>(
+    graph: &Graph<V, W>,
+    start: GraphNodeIndex,
+) -> Option<ShortestPathTree<W>> {
+    dag_tree(graph, &[start], |candidate, distance| candidate < distance)
+}
+
+/// The most expensive paths from start to all nodes in DAG where edges are weights,
+/// by relaxing edges in topological order, in O(V + E).
+/// 'distances' of returned tree are costs of the longest paths.
+/// Returns None if graph has a cycle.
+pub fn dag_longest_paths_in_weighted_indexed_graph<V, W: Weight>(
+    graph: &Graph<V, W>,
+    start: GraphNodeIndex,
+) -> Option<ShortestPathTree<W>> {
+    dag_tree(graph, &[start], |candidate, distance| candidate > distance)
+}
+
+/// Critical path of DAG where nodes are tasks and edges are dependencies with durations:
+/// the most expensive path in the whole graph, which is the minimal time needed to finish all tasks.
+/// Returns None if graph has a cycle or no nodes.
+pub fn critical_path_in_weighted_indexed_graph<V, W: Weight>(
+    graph: &Graph<V, W>,
+) -> Option<ShortestPath<GraphNodeIndex, W>> {
+    // every node can start the critical path
+    let all_nodes: Vec<GraphNodeIndex> = (0..graph.nodes.len()).collect();
+    let tree = dag_tree(graph, &all_nodes, |candidate, distance| {
+        candidate > distance
+    })?;
+    let (end, cost) = tree
+        .distances
+        .iter()
+        .enumerate()
+        .filter_map(|(node, distance)| Some((node, distance.as_ref()?)))
+        .max_by(|(_, a), (_, b)| a.cmp(b))?;
+    Some(ShortestPath {
+        nodes: reconstruct_path(&tree.previous, end),
+        cost: cost.clone(),
+    })
+}
+
+/// Relax edges in topological order, starting from all 'starts' (with zero distance),
+/// 'better' tells if candidate distance should replace current one.
+/// 'start' of returned tree is the first of 'starts'.
+fn dag_tree<V, W, F>(
+    graph: &Graph<V, W>,
+    starts: &[GraphNodeIndex],
+    better: F,
+) -> Option<ShortestPathTree<W>>
+where
+    W: Weight,
+    F: Fn(&W, &W) -> bool,
+{
+    let order = topological_order(graph)?;
+    let mut distances: Vec<Option<W>> = vec![None; graph.nodes.len()]; // None means not reachable
+    let mut previous = vec![None; graph.nodes.len()];
+    for &start in starts {
+        distances[start] = Some(W::zero());
+    }
+
+    // when node is processed, all paths to it were already relaxed
+    for current in order {
+        let Some(current_distance) = distances[current].clone() else {
+            continue;
+        };
+        for (neighbour, weight) in &graph.edges[current] {
+            let Some(neighbour_distance) = current_distance.checked_add(weight) else {
+                continue; // overflow
+            };
+            if distances[*neighbour]
+                .as_ref()
+                .is_none_or(|distance| better(&neighbour_distance, distance))
+            {
+                distances[*neighbour] = Some(neighbour_distance);
+                previous[*neighbour] = Some(current);
+            }
+        }
+    }
+
+    Some(ShortestPathTree {
+        start: starts.first().copied().unwrap_or_default(),
+        distances,
+        previous,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::graph::make_test_weighted_indexed_graph;
+    use crate::search::bellman_ford::{
+        bellman_ford_single_source_in_weighted_indexed_graph, Relaxation,
+    };
+
+    /// Project plan: tasks with edges 'task -> dependent task' weighted by duration of the task.
+    ///   0 (design, 3) -> 1 (backend, 5) -> 3 (integration, 2) -> 4 (release)
+    ///   0 (design, 3) -> 2 (frontend, 4) -> 3
+    ///   5 (docs, 1) -> 4
+    fn make_test_dag() -> Graph<&'static str, i64> {
+        Graph {
+            nodes: vec![
+                "design",
+                "backend",
+                "frontend",
+                "integration",
+                "release",
+                "docs",
+            ],
+            edges: vec![
+                vec![(1, 3), (2, 3)],
+                vec![(3, 5)],
+                vec![(3, 4)],
+                vec![(4, 2)],
+                vec![],
+                vec![(4, 1)],
+            ],
+        }
+    }
+
+    #[test]
+    fn topological_order_works() {
+        let dag = make_test_dag();
+        let order = topological_order(&dag).unwrap();
+        let mut position = vec![0; order.len()];
+        for (i, &node) in order.iter().enumerate() {
+            position[node] = i;
+        }
+        for (from, neighbours) in dag.edges.iter().enumerate() {
+            for &(to, _) in neighbours {
+                assert!(position[from] < position[to]);
+            }
+        }
+
+        // test graph is undirected, so it has cycles
+        assert_eq!(topological_order(&make_test_weighted_indexed_graph()), None);
+        assert_eq!(
+            dag_shortest_paths_in_weighted_indexed_graph(&make_test_weighted_indexed_graph(), 0),
+            None
+        );
+    }
+
+    #[test]
+    fn dag_paths_work() {
+        let mut dag = make_test_dag();
+        // negative edge: frontend gets rebate
+        dag.edges[0][1].1 = -3;
+
+        let shortest = dag_shortest_paths_in_weighted_indexed_graph(&dag, 0).unwrap();
+        assert_eq!(shortest.path(4).unwrap().nodes, [0, 2, 3, 4]);
+        assert_eq!(shortest.distances[4], Some(3));
+        assert_eq!(shortest.path(5), None);
+        // the same as Bellman–Ford
+        assert_eq!(
+            shortest.distances,
+            bellman_ford_single_source_in_weighted_indexed_graph(&dag, 0, Relaxation::Rounds)
+                .unwrap()
+                .distances
+        );
+
+        let longest = dag_longest_paths_in_weighted_indexed_graph(&dag, 0).unwrap();
+        assert_eq!(longest.path(4).unwrap().nodes, [0, 1, 3, 4]);
+        assert_eq!(longest.distances[4], Some(10));
+        assert_eq!(longest.distances[2], Some(-3));
+    }
+
+    #[test]
+    fn critical_path_works() {
+        let dag = make_test_dag();
+        let critical = critical_path_in_weighted_indexed_graph(&dag).unwrap();
+        assert_eq!(critical.nodes, [0, 1, 3, 4]);
+        assert_eq!(critical.cost, 10);
+
+        let single: Graph<(), u32> = Graph {
+            nodes: vec![()],
+            edges: vec![vec![]],
+        };
+        assert_eq!(
+            critical_path_in_weighted_indexed_graph(&single),
+            Some(ShortestPath {
+                nodes: vec![0],
+                cost: 0
+            })
+        );
+        let empty: Graph<(), u32> = Graph {
+            nodes: vec![],
+            edges: vec![],
+        };
+        assert_eq!(critical_path_in_weighted_indexed_graph(&empty), None);
+    }
+}
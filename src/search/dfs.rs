@@ -1,102 +1,200 @@
-use std::{
-    collections::{HashMap, HashSet},
-    rc::Rc,
-};
+use crate::data::graph::{Graph, GraphNodeIndex, LinkedNode};
+use crate::data::visit::{LinkedNodes, Neighbors, VisitMap};
+use crate::search::shortest_path::reconstruct_path_in_map;
 
-use crate::data::graph::{Graph, GraphNode, GraphNodeIndex};
+/// Search for (any) path in unweighted graph of any representation (see data::visit)
+/// by using Depth First Search.
+/// Returns None if target is not reachable.
+pub fn dfs_path<G: Neighbors>(
+    graph: &G,
+    start: G::NodeId,
+    target: G::NodeId,
+) -> Option<Vec<G::NodeId>> {
+    let mut lifo = Vec::new();
+    let mut seen: G::Map<()> = graph.visit_map();
+    let mut previous: G::Map<G::NodeId> = graph.visit_map();
+    if !graph.contains_node(&start) {
+        return None;
+    }
+
+    // node is pushed together with the node which discovered it,
+    // which becomes its parent when the node is visited
+    lifo.push((start, None));
+
+    while let Some((current, parent)) = lifo.pop() {
+        if seen.contains(&current) {
+            continue;
+        }
+        seen.insert(current.clone(), ());
+        if let Some(parent) = parent {
+            previous.insert(current.clone(), parent);
+        }
+        if seen.contains(&target) {
+            break;
+        }
 
+        graph.for_each_neighbour(&current, |neighbour, _| {
+            if !seen.contains(neighbour) {
+                lifo.push((neighbour.clone(), Some(current.clone())));
+            }
+        });
+    }
+
+    seen.contains(&target)
+        .then(|| reconstruct_path_in_map(&previous, target))
+}
+
+/// Search for (any) path in unweighted graph by using Depth First Search.
+/// Returns None if target is not reachable.
 pub fn dfs_path_in_unweighted_indexed_graph<V, E>(
     graph: &Graph<V, E>,
     start: GraphNodeIndex,
     target: GraphNodeIndex,
-) -> Vec<GraphNodeIndex> {
-    let mut path = Vec::new();
-    let mut lifo = Vec::new();
-    let mut seen = vec![false; graph.nodes.len()];
-    let mut previous: Vec<Option<GraphNodeIndex>> = vec![None; graph.nodes.len()];
-    let mut prev = None;
-
-    lifo.push(start);
+) -> Option<Vec<GraphNodeIndex>> {
+    dfs_path(graph, start, target)
+}
 
-    while let Some(current) = lifo.pop() {
-        if !seen[current] {
-            previous[current] = prev;
-            prev = Some(current); // for next iteration of th loop
+/// Search for path in unweighted graph with at most 'max_depth' edges, by using Depth First Search
+/// which does not go deeper than 'max_depth'.
+/// Returns None if there is no such path.
+pub fn depth_limited_dfs_path_in_unweighted_indexed_graph<V, E>(
+    graph: &Graph<V, E>,
+    start: GraphNodeIndex,
+    target: GraphNodeIndex,
+    max_depth: usize,
+) -> Option<Vec<GraphNodeIndex>> {
+    all_simple_paths_in_indexed_graph(graph, start, target, max_depth).next()
+}
 
-            seen[current] = true;
-            if current == target {
-                break;
-            }
+/// Search for the shortest path (with the least edges, at most 'max_depth') in unweighted graph,
+/// by using Iterative Deepening Depth First Search: depth-limited DFS with limits 0, 1, ..., max_depth.
+/// Uses memory proportional to depth only (unlike BFS), for the cost of visiting shallow nodes again.
+/// Returns None if there is no such path.
+pub fn iddfs_path_in_unweighted_indexed_graph<V, E>(
+    graph: &Graph<V, E>,
+    start: GraphNodeIndex,
+    target: GraphNodeIndex,
+    max_depth: usize,
+) -> Option<Vec<GraphNodeIndex>> {
+    for depth in 0..=max_depth {
+        let mut paths = all_simple_paths_in_indexed_graph(graph, start, target, depth);
+        if let Some(path) = paths.next() {
+            return Some(path);
         }
-
-        for &(neighbour, _) in &graph.edges[current] {
-            if !seen[neighbour] {
-                lifo.push(neighbour);
-            }
+        if !paths.cut_off {
+            return None; // nothing was cut off by the limit, so going deeper will not help
         }
     }
+    None
+}
 
-    path.push(target);
-    let mut current = target;
-    while let Some(parent) = previous[current] {
-        path.push(parent);
-        current = parent
+/// All simple (without repeated nodes) paths from start to target with at most 'max_length' edges,
+/// found lazily by Depth First Search.
+/// Paths are sequences of nodes, so with parallel edges the same path is returned once per edge.
+pub fn all_simple_paths_in_indexed_graph<V, E>(
+    graph: &Graph<V, E>,
+    start: GraphNodeIndex,
+    target: GraphNodeIndex,
+    max_length: usize,
+) -> SimplePaths<'_, V, E> {
+    SimplePaths {
+        graph,
+        start: Some(start),
+        target,
+        max_length,
+        path: Vec::new(),
+        next_edge: Vec::new(),
+        on_path: vec![false; graph.nodes.len()],
+        cut_off: false,
     }
-    path.reverse();
+}
 
-    path
+pub struct SimplePaths<'a, V, E> {
+    graph: &'a Graph<V, E>,
+    /// Start node, until it is entered.
+    start: Option<GraphNodeIndex>,
+    target: GraphNodeIndex,
+    max_length: usize,
+    /// Current path from start.
+    path: Vec<GraphNodeIndex>,
+    /// For every node of path, index of its next edge to follow.
+    next_edge: Vec<usize>,
+    on_path: Vec<bool>,
+    /// Some path was not extended because of 'max_length'.
+    cut_off: bool,
 }
 
-fn dfs_path_in_unweighted_linked_graph<V, E>(
-    start: Rc<GraphNode<V, E>>,
-    target: Rc<GraphNode<V, E>>,
-) -> Vec<Rc<GraphNode<V, E>>> {
-    let mut path = Vec::new();
-    let mut previous = HashMap::new();
-    let mut lifo = Vec::new();
-    let mut seen = HashSet::new();
-
-    lifo.push(start.clone());
-
-    let mut prev = None;
-    while let Some(current) = lifo.pop() {
-        if !seen.contains(&current) {
-            previous.insert(current.clone(), prev);
-            seen.insert(current.clone());
-            prev = Some(current.clone());
-            if current == target {
-                break;
-            }
+impl<V, E> SimplePaths<'_, V, E> {
+    /// Extend path with node, returns the path if target is reached.
+    fn enter(&mut self, node: GraphNodeIndex) -> Option<Vec<GraphNodeIndex>> {
+        self.path.push(node);
+        self.next_edge.push(0);
+        self.on_path[node] = true;
+        (node == self.target).then(|| self.path.clone())
+    }
+
+    fn leave(&mut self) {
+        if let Some(node) = self.path.pop() {
+            self.next_edge.pop();
+            self.on_path[node] = false;
         }
+    }
+}
 
-        for (neighbour, _) in current.neighbours.borrow().iter() {
-            if !seen.contains(neighbour) {
-                lifo.push(neighbour.clone())
+impl<V, E> Iterator for SimplePaths<'_, V, E> {
+    type Item = Vec<GraphNodeIndex>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(start) = self.start.take() {
+            if let Some(path) = self.enter(start) {
+                return Some(path);
             }
         }
-    }
 
-    path.push(target.clone());
-    let mut current = target.clone();
-    while let Some(parrent) = &previous[&current] {
-        path.push(parrent.clone());
-        current = parrent.clone();
-    }
+        while let Some(&current) = self.path.last() {
+            let edge_index = self.next_edge[self.path.len() - 1];
+            let neighbours = &self.graph.edges[current];
+            // simple path cannot go through target
+            if current == self.target || edge_index >= neighbours.len() {
+                self.leave();
+                continue;
+            }
+            self.next_edge[self.path.len() - 1] += 1;
 
-    path.reverse();
+            let neighbour = neighbours[edge_index].0;
+            if self.on_path[neighbour] {
+                continue;
+            }
+            if self.path.len() > self.max_length {
+                self.cut_off = true;
+                continue;
+            }
+            if let Some(path) = self.enter(neighbour) {
+                return Some(path);
+            }
+        }
+        None
+    }
+}
 
-    path
+/// Search for (any) path in unweighted linked graph by using Depth First Search.
+/// Returns None if target is not reachable.
+pub fn dfs_path_in_unweighted_linked_graph<N: LinkedNode>(start: N, target: N) -> Option<Vec<N>> {
+    dfs_path(&LinkedNodes::new(), start, target)
 }
 
 #[cfg(test)]
 mod tests {
     use crate::data::graph::{
         make_test_unweighted_indexed_graph, make_test_unweighted_linked_graph_nodes,
+        make_test_weighted_indexed_graph, GraphNode,
     };
+    use crate::search::bfs::bfs_single_source_in_indexed_graph;
+    use crate::search::k_shortest_paths::k_shortest_paths_in_weighted_indexed_graph;
 
     use super::*;
 
-    use std::collections::HashSet;
+    use std::{collections::HashSet, rc::Rc};
 
     ///////////--- Test graph --- /////////
     //                 //                //
@@ -115,7 +213,7 @@ mod tests {
         let graph = make_test_unweighted_indexed_graph();
         let start = 0;
         let end = 6;
-        let path = dfs_path_in_unweighted_indexed_graph(&graph, start, end);
+        let path = dfs_path_in_unweighted_indexed_graph(&graph, start, end).unwrap();
 
         let unique: HashSet<GraphNodeIndex> = path.iter().copied().collect();
         assert_eq!(unique.len(), path.len());
@@ -133,11 +231,126 @@ mod tests {
         }
     }
 
+    #[test]
+    fn dfs_path_follows_edges_after_dead_end() {
+        // DFS visits 0, 2, 3 (dead end) and then 1, which was discovered by 0 and not by 3
+        let graph = Graph {
+            nodes: vec!['a', 'b', 'c', 'd', 'e'],
+            edges: vec![
+                vec![(1, ()), (2, ())],
+                vec![],
+                vec![(3, ())],
+                vec![],
+                vec![],
+            ],
+        };
+        assert_eq!(
+            dfs_path_in_unweighted_indexed_graph(&graph, 0, 1),
+            Some(vec![0, 1])
+        );
+        assert_eq!(
+            dfs_path_in_unweighted_indexed_graph(&graph, 0, 3),
+            Some(vec![0, 2, 3])
+        );
+        assert_eq!(dfs_path_in_unweighted_indexed_graph(&graph, 0, 4), None);
+        assert_eq!(
+            dfs_path_in_unweighted_indexed_graph(&graph, 2, 2),
+            Some(vec![2])
+        );
+
+        // every found path follows edges
+        let graph = make_test_unweighted_indexed_graph();
+        for start in 0..8 {
+            for end in 0..8 {
+                let path = dfs_path_in_unweighted_indexed_graph(&graph, start, end).unwrap();
+                assert_eq!((path[0], path[path.len() - 1]), (start, end));
+                for w in path.windows(2) {
+                    assert!(graph.edges[w[0]].iter().any(|&(n, _)| n == w[1]));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn iddfs_finds_shortest_path() {
+        let graph = make_test_unweighted_indexed_graph();
+        for start in 0..8 {
+            let bfs = bfs_single_source_in_indexed_graph(&graph, start);
+            for end in 0..8 {
+                let path = iddfs_path_in_unweighted_indexed_graph(&graph, start, end, 8).unwrap();
+                assert_eq!(Some(path.len() - 1), bfs.distances[end]);
+                assert_eq!((path[0], path[path.len() - 1]), (start, end));
+            }
+        }
+
+        // 'a' -> 'g' needs 2 edges
+        assert_eq!(
+            iddfs_path_in_unweighted_indexed_graph(&graph, 0, 6, 1),
+            None
+        );
+        assert_eq!(
+            iddfs_path_in_unweighted_indexed_graph(&graph, 0, 6, 2),
+            Some(vec![0, 1, 6])
+        );
+        assert_eq!(
+            depth_limited_dfs_path_in_unweighted_indexed_graph(&graph, 0, 6, 1),
+            None
+        );
+        let path = depth_limited_dfs_path_in_unweighted_indexed_graph(&graph, 0, 6, 4).unwrap();
+        assert!(path.len() - 1 <= 4);
+
+        // unreachable target stops deepening before the limit
+        let mut graph = graph;
+        graph.nodes.push('i');
+        graph.edges.push(Vec::new());
+        assert_eq!(
+            iddfs_path_in_unweighted_indexed_graph(&graph, 0, 8, usize::MAX - 1),
+            None
+        );
+    }
+
+    #[test]
+    fn all_simple_paths_works() {
+        let graph = make_test_unweighted_indexed_graph();
+        let paths: Vec<_> = all_simple_paths_in_indexed_graph(&graph, 0, 5, 2).collect();
+        assert_eq!(paths, [vec![0, 4, 5]]);
+
+        // the same paths as all found by Yen's algorithm
+        let weighted = make_test_weighted_indexed_graph();
+        for (start, end) in [(0, 5), (1, 7), (6, 6)] {
+            let mut paths: Vec<_> =
+                all_simple_paths_in_indexed_graph(&graph, start, end, 8).collect();
+            for path in &paths {
+                let unique: HashSet<GraphNodeIndex> = path.iter().copied().collect();
+                assert_eq!(unique.len(), path.len());
+            }
+            let mut yen: Vec<_> = k_shortest_paths_in_weighted_indexed_graph(&weighted, start, end)
+                .map(|path| path.nodes)
+                .collect();
+            paths.sort();
+            yen.sort();
+            assert_eq!(paths, yen);
+
+            // bound on length
+            for max_length in 0..5 {
+                let mut bounded = all_simple_paths_in_indexed_graph(&graph, start, end, max_length)
+                    .collect::<Vec<_>>();
+                let expected: Vec<_> = paths
+                    .iter()
+                    .filter(|path| path.len() - 1 <= max_length)
+                    .cloned()
+                    .collect();
+                bounded.sort();
+                assert_eq!(bounded, expected);
+            }
+        }
+    }
+
     #[test]
     fn dfs_path_works_for_unweighted_linked_graph() {
-        let (start, end) = make_test_unweighted_linked_graph_nodes('a', Some('g'));
+        let (_graph, start, end) = make_test_unweighted_linked_graph_nodes('a', Some('g'));
         let end = end.unwrap();
-        let path = dfs_path_in_unweighted_linked_graph(start.clone(), end.clone());
+        let path = dfs_path_in_unweighted_linked_graph(start.clone(), end.clone()).unwrap();
 
         let unique: HashSet<Rc<GraphNode<char, ()>>> = path.iter().cloned().collect();
 
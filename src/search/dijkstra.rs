@@ -1,168 +1,225 @@
-use crate::data::graph::{Graph, GraphNode, GraphNodeIndex};
-use std::{
-    cmp::Ordering,
-    collections::{BinaryHeap, HashMap},
-    rc::Rc,
-};
+use crate::data::graph::{Graph, GraphNodeIndex, LinkedNode};
+use crate::data::visit::{LinkedNodes, Neighbors, VisitMap};
+use crate::data::weight::Weight;
+use crate::search::shortest_path::{reconstruct_path_in_map, ShortestPath, ShortestPathTree};
+use std::{cmp::Ordering, collections::BinaryHeap};
 
-struct ShortestDistance {
-    index: usize,
-    value: u64,
+pub(crate) struct ShortestDistance<W> {
+    pub(crate) index: usize,
+    pub(crate) value: W,
 }
-impl PartialEq for ShortestDistance {
+impl<W: Weight> PartialEq for ShortestDistance<W> {
     fn eq(&self, other: &Self) -> bool {
         self.value == other.value
     }
 }
-impl PartialOrd for ShortestDistance {
+impl<W: Weight> PartialOrd for ShortestDistance<W> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
-impl Ord for ShortestDistance {
+impl<W: Weight> Ord for ShortestDistance<W> {
     fn cmp(&self, other: &Self) -> Ordering {
-        other.value.partial_cmp(&self.value).unwrap()
+        other.value.cmp(&self.value)
     }
 }
-impl Eq for ShortestDistance {}
+impl<W: Weight> Eq for ShortestDistance<W> {}
 
-pub fn dijkstra_shortest_path_in_weighted_indexed_graph<V>(
-    graph: &Graph<V, u64>,
-    start: GraphNodeIndex,
-    target: GraphNodeIndex,
-) -> Vec<GraphNodeIndex> {
-    let mut path = Vec::new();
-    let mut distances = vec![u64::MAX; graph.nodes.len()];
-    let mut heap = BinaryHeap::new(); // use min-heap to always follow shortest/cheapest choice
-    let mut previous = vec![None; graph.nodes.len()];
-
-    distances[start] = 0;
-    heap.push(ShortestDistance {
-        index: start,
-        value: 0,
-    });
-
-    while let Some(ShortestDistance {
-        index: current,
-        value: current_distance,
-    }) = heap.pop()
-    {
-        if current == target {
-            break;
-        }
-
-        for &(neighbour, neighbour_edge) in &graph.edges[current] {
-            let neighbour_distance = current_distance + neighbour_edge;
-            if neighbour_distance < distances[neighbour] {
-                let shortest_distance = ShortestDistance {
-                    index: neighbour,
-                    value: neighbour_distance,
-                };
-                heap.push(shortest_distance);
-                distances[neighbour] = neighbour_distance;
-                previous[neighbour] = Some(current);
-            }
-        }
-    }
-
-    path.push(target);
-    let mut current = target;
-    while let Some(parrent) = previous[current] {
-        path.push(parrent);
-        current = parrent;
-    }
-    path.reverse();
-
-    let shortest_path_value = distances[target];
-    println!("shortest_path_value: {shortest_path_value}");
-
-    path
+struct ShortestDistanceForGraphNode<N, W> {
+    node: N,
+    value: W,
 }
-
-struct ShortestDistanceForGraphNode<V> {
-    node: Rc<GraphNode<V, u64>>,
-    value: u64,
-}
-impl<V> PartialEq for ShortestDistanceForGraphNode<V> {
+impl<N, W: Weight> PartialEq for ShortestDistanceForGraphNode<N, W> {
     fn eq(&self, other: &Self) -> bool {
         self.value == other.value
     }
 }
-impl<V> PartialOrd for ShortestDistanceForGraphNode<V> {
+impl<N, W: Weight> PartialOrd for ShortestDistanceForGraphNode<N, W> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
-impl<V> Ord for ShortestDistanceForGraphNode<V> {
+impl<N, W: Weight> Ord for ShortestDistanceForGraphNode<N, W> {
     fn cmp(&self, other: &Self) -> Ordering {
-        other.value.partial_cmp(&self.value).unwrap()
+        other.value.cmp(&self.value)
     }
 }
-impl<V> Eq for ShortestDistanceForGraphNode<V> {}
+impl<N, W: Weight> Eq for ShortestDistanceForGraphNode<N, W> {}
 
-pub fn dijkstra_shortest_path_in_weighted_linked_graph<V>(
-    start_node: Rc<GraphNode<V, u64>>,
-    target_node: Rc<GraphNode<V, u64>>,
-) -> Vec<Rc<GraphNode<V, u64>>> {
-    let mut path = Vec::new();
-    let mut distances = HashMap::new();
+/// Search for the cheapest path in graph of any representation (see data::visit) where edges are weights.
+/// Returns None if target is not reachable.
+pub fn dijkstra_shortest_path<G>(
+    graph: &G,
+    start: G::NodeId,
+    target: G::NodeId,
+) -> Option<ShortestPath<G::NodeId, G::Edge>>
+where
+    G: Neighbors,
+    G::Edge: Weight,
+{
+    dijkstra_shortest_path_by(graph, start, target, |edge| edge.clone())
+}
+
+/// Search for the cheapest path in graph of any representation with any edge data,
+/// 'weight' extracts weight from the edge.
+/// Paths which cost does not fit in W (overflow) are not taken into account.
+/// Returns None if target is not reachable.
+pub fn dijkstra_shortest_path_by<G, W, F>(
+    graph: &G,
+    start: G::NodeId,
+    target: G::NodeId,
+    weight: F,
+) -> Option<ShortestPath<G::NodeId, W>>
+where
+    G: Neighbors,
+    W: Weight,
+    F: Fn(&G::Edge) -> W,
+{
+    let (distances, previous) = dijkstra_tree(graph, start, Some(&target), weight);
+    let cost = distances.get(&target)?.clone();
+    Some(ShortestPath {
+        nodes: reconstruct_path_in_map(&previous, target),
+        cost,
+    })
+}
+
+/// Search for the cheapest path in graph where edges are weights.
+/// Returns None if target is not reachable.
+pub fn dijkstra_shortest_path_in_weighted_indexed_graph<V, W: Weight>(
+    graph: &Graph<V, W>,
+    start: GraphNodeIndex,
+    target: GraphNodeIndex,
+) -> Option<ShortestPath<GraphNodeIndex, W>> {
+    dijkstra_shortest_path(graph, start, target)
+}
+
+/// Search for the cheapest path in graph with any edge data, 'weight' extracts weight from the edge.
+/// Paths which cost does not fit in W (overflow) are not taken into account.
+/// Returns None if target is not reachable.
+pub fn dijkstra_shortest_path_in_indexed_graph_by<V, E, W, F>(
+    graph: &Graph<V, E>,
+    start: GraphNodeIndex,
+    target: GraphNodeIndex,
+    weight: F,
+) -> Option<ShortestPath<GraphNodeIndex, W>>
+where
+    W: Weight,
+    F: Fn(&E) -> W,
+{
+    dijkstra_shortest_path_by(graph, start, target, weight)
+}
+
+/// The cheapest paths from start to all nodes in graph where edges are weights.
+pub fn dijkstra_single_source_in_weighted_indexed_graph<V, W: Weight>(
+    graph: &Graph<V, W>,
+    start: GraphNodeIndex,
+) -> ShortestPathTree<W> {
+    dijkstra_single_source_in_indexed_graph_by(graph, start, |edge| edge.clone())
+}
+
+/// The cheapest paths from start to all nodes in graph with any edge data, 'weight' extracts weight from the edge.
+pub fn dijkstra_single_source_in_indexed_graph_by<V, E, W, F>(
+    graph: &Graph<V, E>,
+    start: GraphNodeIndex,
+    weight: F,
+) -> ShortestPathTree<W>
+where
+    W: Weight,
+    F: Fn(&E) -> W,
+{
+    let (distances, previous) = dijkstra_tree(graph, start, None, weight);
+    ShortestPathTree {
+        start,
+        distances,
+        previous,
+    }
+}
+
+/// Search for the cheapest path in linked graph where edges are weights.
+/// Paths which cost does not fit in W (overflow) are not taken into account.
+/// Returns None if target is not reachable.
+pub fn dijkstra_shortest_path_in_weighted_linked_graph<N, W>(
+    start_node: N,
+    target_node: N,
+) -> Option<ShortestPath<N, W>>
+where
+    N: LinkedNode<Edge = W>,
+    W: Weight,
+{
+    dijkstra_shortest_path(&LinkedNodes::new(), start_node, target_node)
+}
+
+/// Dijkstra until target is reached (or all reachable nodes if there is no target).
+/// Returns distances and predecessors of reached nodes.
+fn dijkstra_tree<G, W, F>(
+    graph: &G,
+    start: G::NodeId,
+    target: Option<&G::NodeId>,
+    weight: F,
+) -> (G::Map<W>, G::Map<G::NodeId>)
+where
+    G: Neighbors,
+    W: Weight,
+    F: Fn(&G::Edge) -> W,
+{
+    let mut distances: G::Map<W> = graph.visit_map(); // no distance means infinity
     let mut heap = BinaryHeap::new(); // use min-heap to always follow shortest/cheapest choice
-    let mut previous = HashMap::new();
+    let mut previous: G::Map<G::NodeId> = graph.visit_map();
+    let mut done: G::Map<()> = graph.visit_map();
+    if !graph.contains_node(&start) {
+        return (distances, previous);
+    }
 
-    distances.insert(start_node.clone(), 0_u64);
+    distances.insert(start.clone(), W::zero());
     heap.push(ShortestDistanceForGraphNode {
-        node: start_node.clone(),
-        value: 0,
+        node: start,
+        value: W::zero(),
     });
-    previous.insert(start_node.clone(), None);
 
     while let Some(ShortestDistanceForGraphNode {
-        node: current_node,
+        node: current,
         value: current_distance,
     }) = heap.pop()
     {
-        if current_node == target_node {
+        if distances
+            .get(&current)
+            .is_some_and(|distance| current_distance > *distance)
+        {
+            continue; // outdated heap entry
+        }
+        done.insert(current.clone(), ());
+        if target.is_some_and(|target| done.contains(target)) {
             break;
         }
 
-        for (neighbour_node, neighbour_edge) in current_node.neighbours.borrow().iter() {
-            let neighbour_distance = current_distance + neighbour_edge;
-            if neighbour_distance
-                < distances
-                    .get(&neighbour_node.clone())
-                    .map_or(u64::MAX, |d| *d)
+        graph.for_each_neighbour(&current, |neighbour, neighbour_edge| {
+            let Some(neighbour_distance) = current_distance.checked_add(&weight(neighbour_edge))
+            else {
+                return; // overflow
+            };
+            if distances
+                .get(neighbour)
+                .is_none_or(|distance| neighbour_distance < *distance)
             {
-                let shortest_distance = ShortestDistanceForGraphNode {
-                    node: neighbour_node.clone(),
-                    value: neighbour_distance,
-                };
-                heap.push(shortest_distance);
-                distances.insert(neighbour_node.clone(), neighbour_distance);
-                previous.insert(neighbour_node.clone(), Some(current_node.clone()));
+                heap.push(ShortestDistanceForGraphNode {
+                    node: neighbour.clone(),
+                    value: neighbour_distance.clone(),
+                });
+                distances.insert(neighbour.clone(), neighbour_distance);
+                previous.insert(neighbour.clone(), current.clone());
             }
-        }
-    }
-
-    path.push(target_node.clone());
-    let mut current_node = target_node.clone();
-    while let Some(parrent_node) = &previous[&current_node] {
-        path.push(parrent_node.clone());
-        current_node = parrent_node.clone();
+        });
     }
 
-    path.reverse();
-
-    let shortest_path_value = distances[&target_node];
-    println!("shortest_path_value: {shortest_path_value}");
-    path
+    (distances, previous)
 }
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashSet;
+    use std::{collections::HashSet, rc::Rc};
 
     use crate::data::graph::{
-        make_test_weighted_indexed_graph, make_test_weighted_linked_graph_nodes,
+        make_test_weighted_indexed_graph, make_test_weighted_linked_graph_nodes, GraphNode,
     };
 
     use super::*;
@@ -186,12 +243,51 @@ mod tests {
         // GraphNodeIndex:  0,   1,   2,   3,   4,   5,   6,   7
         //                 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'
 
-        let shortest_path = dijkstra_shortest_path_in_weighted_indexed_graph(&graph, 0, 5);
-        assert_eq!(shortest_path, [0, 4, 5]);
-        let shortest_path = dijkstra_shortest_path_in_weighted_indexed_graph(&graph, 1, 5);
-        assert_eq!(shortest_path, [1, 0, 4, 5]);
-        let shortest_path = dijkstra_shortest_path_in_weighted_indexed_graph(&graph, 0, 7);
-        assert_eq!(shortest_path, [0, 3, 7]);
+        let shortest_path = dijkstra_shortest_path_in_weighted_indexed_graph(&graph, 0, 5).unwrap();
+        assert_eq!(shortest_path.nodes, [0, 4, 5]);
+        assert_eq!(shortest_path.cost, 2);
+        let shortest_path = dijkstra_shortest_path_in_weighted_indexed_graph(&graph, 1, 5).unwrap();
+        assert_eq!(shortest_path.nodes, [1, 0, 4, 5]);
+        assert_eq!(shortest_path.cost, 3);
+        let shortest_path = dijkstra_shortest_path_in_weighted_indexed_graph(&graph, 0, 7).unwrap();
+        assert_eq!(shortest_path.nodes, [0, 3, 7]);
+        assert_eq!(shortest_path.cost, 3);
+    }
+
+    #[test]
+    fn dijkstra_single_source_works() {
+        let mut graph = make_test_weighted_indexed_graph();
+        // disconnected node
+        graph.nodes.push('i');
+        graph.edges.push(Vec::new());
+        assert_eq!(
+            dijkstra_shortest_path_in_weighted_indexed_graph(&graph, 0, 8),
+            None
+        );
+
+        let tree = dijkstra_single_source_in_weighted_indexed_graph(&graph, 1);
+        assert_eq!(
+            tree.distances,
+            [
+                Some(1),
+                Some(0),
+                Some(1),
+                Some(2),
+                Some(2),
+                Some(3),
+                Some(1),
+                Some(4),
+                None
+            ]
+        );
+        assert_eq!(tree.path(5).unwrap().nodes, [1, 0, 4, 5]);
+        assert_eq!(tree.path(8), None);
+        for target in 0..8 {
+            assert_eq!(
+                tree.path(target),
+                dijkstra_shortest_path_in_weighted_indexed_graph(&graph, 1, target)
+            );
+        }
     }
 
     #[test]
@@ -200,9 +296,11 @@ mod tests {
         //                 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'
 
         // 'a' -> 'f', i.e. 0->5
-        let (start, end) = make_test_weighted_linked_graph_nodes('a', Some('f'));
+        let (_graph, start, end) = make_test_weighted_linked_graph_nodes('a', Some('f'));
         let end = end.unwrap();
-        let path = dijkstra_shortest_path_in_weighted_linked_graph(start.clone(), end.clone());
+        let path = dijkstra_shortest_path_in_weighted_linked_graph(start.clone(), end.clone())
+            .unwrap()
+            .nodes;
         let unique: HashSet<Rc<GraphNode<char, u64>>> = path.iter().cloned().collect();
         assert_eq!(unique.len(), path.len());
         assert_eq!(path[0], start);
@@ -224,9 +322,11 @@ mod tests {
         );
 
         // 'b' -> 'f', i.e. 1->5
-        let (start, end) = make_test_weighted_linked_graph_nodes('b', Some('f'));
+        let (_graph, start, end) = make_test_weighted_linked_graph_nodes('b', Some('f'));
         let end = end.unwrap();
-        let path = dijkstra_shortest_path_in_weighted_linked_graph(start.clone(), end.clone());
+        let path = dijkstra_shortest_path_in_weighted_linked_graph(start.clone(), end.clone())
+            .unwrap()
+            .nodes;
         let unique: HashSet<Rc<GraphNode<char, u64>>> = path.iter().cloned().collect();
         assert_eq!(unique.len(), path.len());
         assert_eq!(path[0], start);
@@ -248,9 +348,11 @@ mod tests {
         );
 
         // 'a' -> 'h', i.e. 0->7
-        let (start, end) = make_test_weighted_linked_graph_nodes('a', Some('h'));
+        let (_graph, start, end) = make_test_weighted_linked_graph_nodes('a', Some('h'));
         let end = end.unwrap();
-        let path = dijkstra_shortest_path_in_weighted_linked_graph(start.clone(), end.clone());
+        let path = dijkstra_shortest_path_in_weighted_linked_graph(start.clone(), end.clone())
+            .unwrap()
+            .nodes;
         let unique: HashSet<Rc<GraphNode<char, u64>>> = path.iter().cloned().collect();
         assert_eq!(unique.len(), path.len());
         assert_eq!(path[0], start);
@@ -271,4 +373,53 @@ mod tests {
             ['a', 'd', 'h']
         );
     }
+
+    #[test]
+    fn dijkstra_works_for_any_edge_data_by_weight_closure() {
+        struct Road {
+            #[allow(dead_code)]
+            name: &'static str,
+            minutes: u32,
+        }
+        let road = |name, minutes| Road { name, minutes };
+        let graph: Graph<&str, Road> = Graph {
+            nodes: vec!["home", "shop", "park", "work"],
+            edges: vec![
+                vec![(1, road("main st", 5)), (2, road("park ln", 2))],
+                vec![(3, road("high st", 5))],
+                vec![(1, road("side st", 1)), (3, road("long rd", 10))],
+                vec![],
+            ],
+        };
+        let path =
+            dijkstra_shortest_path_in_indexed_graph_by(&graph, 0, 3, |road| road.minutes).unwrap();
+        assert_eq!(path.nodes, [0, 2, 1, 3]);
+        assert_eq!(path.cost, 8);
+    }
+
+    #[test]
+    fn dijkstra_skips_overflowing_paths() {
+        use crate::data::weight::OrderedFloat;
+
+        // 0 -> 1 -> 2 costs 300, which does not fit in u8
+        let graph: Graph<char, u8> = Graph {
+            nodes: vec!['a', 'b', 'c'],
+            edges: vec![vec![(1, 200), (2, 250)], vec![(2, 100)], vec![]],
+        };
+        let path = dijkstra_shortest_path_in_weighted_indexed_graph(&graph, 0, 2).unwrap();
+        assert_eq!(path.nodes, [0, 2]);
+        assert_eq!(path.cost, 250);
+
+        let graph: Graph<char, OrderedFloat<f64>> = Graph {
+            nodes: vec!['a', 'b', 'c'],
+            edges: vec![
+                vec![(1, OrderedFloat(0.5)), (2, OrderedFloat(1.5))],
+                vec![(2, OrderedFloat(0.75))],
+                vec![],
+            ],
+        };
+        let path = dijkstra_shortest_path_in_weighted_indexed_graph(&graph, 0, 2).unwrap();
+        assert_eq!(path.nodes, [0, 1, 2]);
+        assert_eq!(path.cost, OrderedFloat(1.25));
+    }
 }
@@ -0,0 +1,78 @@
+use crate::search::binary_search::binary_search_by;
+
+/// Exponential (galloping) search for element in sorted slice.
+/// Cheap when element is near the beginning: O(log i), where i is the position of element.
+/// Returns the same as `binary_search`, i.e. Ok(index) or Err(insertion_index).
+pub fn exponential_search<T: Ord>(sorted: &[T], el: &T) -> Result<usize, usize> {
+    exponential_search_with_probes(sorted, el).0
+}
+
+/// Exponential search which also returns number of probes (comparisons with slice elements).
+pub fn exponential_search_with_probes<T: Ord>(
+    sorted: &[T],
+    el: &T,
+) -> (Result<usize, usize>, usize) {
+    if sorted.is_empty() {
+        return (Err(0), 0);
+    }
+
+    // gallop: 1, 2, 4, 8, ... until sorted[bound] >= el
+    let mut probes = 0;
+    let mut bound = 1;
+    while bound < sorted.len() {
+        probes += 1;
+        if sorted[bound] < *el {
+            bound *= 2; // will not overflow, because bound < len <= isize::MAX
+        } else {
+            break;
+        }
+    }
+
+    // here: sorted[left] < el (or left == 0) and el <= sorted[bound] (or bound >= len)
+    let left = bound / 2;
+    let right = (bound + 1).min(sorted.len());
+    let result = binary_search_by(&sorted[left..right], |m| {
+        probes += 1;
+        m.cmp(el)
+    });
+
+    (result.map(|i| i + left).map_err(|i| i + left), probes)
+}
+
+#[test]
+fn exponential_search_works() {
+    let a = [1, 2, 40, 61, 121, 333, 335, 380];
+    assert_eq!(Err(2), exponential_search(&a, &4));
+    assert_eq!(Ok(1), exponential_search(&a, &2));
+    assert_eq!(Ok(4), exponential_search(&a, &121));
+    assert_eq!(Ok(0), exponential_search(&a, &1));
+    assert_eq!(Ok(7), exponential_search(&a, &380));
+    assert_eq!(Err(0), exponential_search(&a, &0));
+    assert_eq!(Err(8), exponential_search(&a, &400));
+    assert_eq!(Err(0), exponential_search(&[], &400));
+
+    // element near the front is found with few probes, even in huge slice
+    let big: Vec<u64> = (0..1_000_000).collect();
+    let (result, probes) = exponential_search_with_probes(&big, &3);
+    assert_eq!(result, Ok(3));
+    assert!(probes <= 5, "probes: {probes}");
+}
+
+#[test]
+fn exponential_search_matches_binary_search() {
+    use crate::rng::XorShift64;
+    use crate::search::binary_search::{binary_search, equal_range};
+
+    let mut rng = XorShift64::new(28);
+    for _ in 0..300 {
+        let len = rng.index(50);
+        let mut a: Vec<u64> = (0..len).map(|_| rng.below(30)).collect();
+        a.sort();
+        for el in 0..32 {
+            match (exponential_search(&a, &el), binary_search(&a, &el)) {
+                (Ok(found), Ok(_)) => assert!(equal_range(&a, &el).contains(&found)),
+                (result, expected) => assert_eq!(result, expected, "{a:?} {el}"),
+            }
+        }
+    }
+}
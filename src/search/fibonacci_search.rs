@@ -0,0 +1,85 @@
+use std::cmp::Ordering;
+
+/// Fibonacci search for element in sorted slice.
+/// Like binary search, but splits the range by Fibonacci numbers (uses only addition/subtraction to find split points).
+/// Returns the same as `binary_search`, i.e. Ok(index) or Err(insertion_index).
+pub fn fibonacci_search<T: Ord>(sorted: &[T], el: &T) -> Result<usize, usize> {
+    fibonacci_search_with_probes(sorted, el).0
+}
+
+/// Fibonacci search which also returns number of probes (comparisons with slice elements).
+pub fn fibonacci_search_with_probes<T: Ord>(sorted: &[T], el: &T) -> (Result<usize, usize>, usize) {
+    let len = sorted.len();
+    // (fk, fk1, fk2) are Fibonacci numbers: F(k), F(k-1), F(k-2)
+    // find the smallest k such that F(k) - 1 >= len
+    let (mut fk, mut fk1, mut fk2) = (1, 1, 0);
+    while fk - 1 < len {
+        (fk, fk1, fk2) = (fk + fk1, fk, fk1);
+    }
+
+    // window: left..(left + F(k) - 1), where elements >= len are treated as +infinity
+    // - everything on the left of window is < el
+    // - first element on the right of window is > el (or it is >= len)
+    let mut probes = 0;
+    let mut left = 0;
+    while fk > 1 {
+        // window is split to: [F(k-1) - 1 elements | probe | F(k-2) - 1 elements]
+        let probe = left + fk1 - 1;
+        let ordering = if probe < len {
+            probes += 1;
+            sorted[probe].cmp(el)
+        } else {
+            Ordering::Greater
+        };
+        match ordering {
+            // go to the left part: k -= 1
+            Ordering::Greater => (fk, fk1, fk2) = (fk1, fk2, fk1 - fk2),
+            // go to the right part: k -= 2
+            Ordering::Less => {
+                left = probe + 1;
+                (fk, fk1, fk2) = (fk2, fk1 - fk2, fk2 - (fk1 - fk2));
+            }
+            Ordering::Equal => return (Ok(probe), probes),
+        }
+    }
+
+    (Err(left), probes)
+}
+
+#[test]
+fn fibonacci_search_works() {
+    let a = [1, 2, 40, 61, 121, 333, 335, 380];
+    assert_eq!(Err(2), fibonacci_search(&a, &4));
+    assert_eq!(Ok(1), fibonacci_search(&a, &2));
+    assert_eq!(Ok(4), fibonacci_search(&a, &121));
+    assert_eq!(Ok(0), fibonacci_search(&a, &1));
+    assert_eq!(Ok(7), fibonacci_search(&a, &380));
+    assert_eq!(Ok(6), fibonacci_search(&a, &335));
+    assert_eq!(Err(0), fibonacci_search(&a, &0));
+    assert_eq!(Err(8), fibonacci_search(&a, &400));
+    assert_eq!(Err(0), fibonacci_search(&[], &400));
+
+    let big: Vec<u64> = (0..1_000_000).collect();
+    let (result, probes) = fibonacci_search_with_probes(&big, &777_777);
+    assert_eq!(result, Ok(777_777));
+    assert!(probes <= 30, "probes: {probes}"); // ~1.44 * log2(n)
+}
+
+#[test]
+fn fibonacci_search_matches_binary_search() {
+    use crate::rng::XorShift64;
+    use crate::search::binary_search::{binary_search, equal_range};
+
+    let mut rng = XorShift64::new(28);
+    for _ in 0..300 {
+        let len = rng.index(50);
+        let mut a: Vec<u64> = (0..len).map(|_| rng.below(30)).collect();
+        a.sort();
+        for el in 0..32 {
+            match (fibonacci_search(&a, &el), binary_search(&a, &el)) {
+                (Ok(found), Ok(_)) => assert!(equal_range(&a, &el).contains(&found)),
+                (result, expected) => assert_eq!(result, expected, "{a:?} {el}"),
+            }
+        }
+    }
+}
@@ -0,0 +1,76 @@
+/// Text searched by substring searches (`kmp_search`, `horspool_search`, `rabin_karp_search`, `z_search`).
+/// Matches are byte indexes, for str they are always char boundaries (like `str::match_indices`).
+pub trait Haystack: AsRef<[u8]> {
+    /// Haystack is UTF-8 text, so positions which are not char boundaries are not matches.
+    const IS_STR: bool = false;
+}
+
+impl Haystack for str {
+    const IS_STR: bool = true;
+}
+impl Haystack for String {
+    const IS_STR: bool = true;
+}
+impl Haystack for [u8] {}
+impl Haystack for Vec<u8> {}
+impl<const N: usize> Haystack for [u8; N] {}
+
+/// Iterator over positions of empty pattern in haystack: every byte index 0..=haystack.len(),
+/// for str only char boundaries (the same as `str::match_indices("")`).
+pub struct EmptyPatternMatches<'a> {
+    haystack: &'a [u8],
+    is_str: bool,
+    position: usize,
+}
+
+impl<'a> EmptyPatternMatches<'a> {
+    pub fn new<H: Haystack + ?Sized>(haystack: &'a H) -> Self {
+        EmptyPatternMatches {
+            haystack: haystack.as_ref(),
+            is_str: H::IS_STR,
+            position: 0,
+        }
+    }
+
+    /// Matches of pattern in haystack when the pattern is empty, None for other patterns.
+    pub fn of_pattern<H: Haystack + ?Sized>(haystack: &'a H, pattern: &[u8]) -> Option<Self> {
+        pattern.is_empty().then(|| Self::new(haystack))
+    }
+}
+
+impl Iterator for EmptyPatternMatches<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.position <= self.haystack.len() {
+            let i = self.position;
+            self.position += 1;
+            // UTF-8 continuation bytes (0b10xxxxxx) are inside of char
+            let is_char_boundary = self.haystack.get(i).is_none_or(|&byte| byte & 0xC0 != 0x80);
+            if !self.is_str || is_char_boundary {
+                return Some(i);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_pattern_matches_char_boundaries_of_str() {
+        let text = "zażółć";
+        let expected: Vec<usize> = text.match_indices("").map(|(i, _)| i).collect();
+        assert_eq!(EmptyPatternMatches::new(text).collect::<Vec<_>>(), expected);
+        assert_eq!(expected, [0, 1, 2, 4, 6, 8, 10]);
+        // bytes have no chars, so every position matches
+        assert_eq!(
+            EmptyPatternMatches::new(text.as_bytes()).count(),
+            text.len() + 1
+        );
+        assert_eq!(EmptyPatternMatches::new("").collect::<Vec<_>>(), [0]);
+        assert!(EmptyPatternMatches::of_pattern(text, b"a").is_none());
+    }
+}
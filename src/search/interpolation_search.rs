@@ -0,0 +1,156 @@
+use crate::search::binary_search::binary_search_by;
+use std::cmp::Ordering;
+
+/// Integer types which can be searched by `interpolation_search`.
+pub trait InterpolationInteger: Copy + Ord {
+    /// Distance between values, computed without overflow (also for MIN..MAX).
+    fn distance(from: Self, to: Self) -> u128;
+}
+
+macro_rules! impl_interpolation_integer {
+    ($($t:ty),*) => {
+        $(
+            impl InterpolationInteger for $t {
+                fn distance(from: Self, to: Self) -> u128 {
+                    // to - from can overflow for signed types, so distance is computed in unsigned type
+                    to.abs_diff(from) as u128
+                }
+            }
+        )*
+    };
+}
+impl_interpolation_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Interpolation search for element in sorted slice of integers.
+/// Guesses position of element from its value, so for evenly spread values it needs O(log log n) probes.
+/// As a safeguard (e.g. for skewed values) it falls back to binary search after log2(n) interpolation probes,
+/// so it is never much worse than `binary_search`.
+/// Returns the same as `binary_search`, i.e. Ok(index) or Err(insertion_index).
+pub fn interpolation_search<T>(sorted: &[T], el: &T) -> Result<usize, usize>
+where
+    T: InterpolationInteger,
+{
+    interpolation_search_with_probes(sorted, el).0
+}
+
+/// Interpolation search which also returns number of probes (comparisons with slice elements).
+pub fn interpolation_search_with_probes<T>(sorted: &[T], el: &T) -> (Result<usize, usize>, usize)
+where
+    T: InterpolationInteger,
+{
+    let mut probes = 0;
+    // search range: sorted[..left] < el and el < sorted[right..]
+    let mut left = 0;
+    let mut right = sorted.len();
+    let mut interpolations_left = usize::BITS - sorted.len().leading_zeros(); // ~ log2(n)
+
+    while left < right && interpolations_left > 0 {
+        interpolations_left -= 1;
+
+        let first = sorted[left];
+        probes += 1;
+        if *el <= first {
+            return (if *el == first { Ok(left) } else { Err(left) }, probes);
+        }
+        let last = sorted[right - 1];
+        probes += 1;
+        if last <= *el {
+            return (
+                if *el == last {
+                    Ok(right - 1)
+                } else {
+                    Err(right)
+                },
+                probes,
+            );
+        }
+
+        // here: first < el < last, so the guess is in left..(right - 1)
+        let guess = T::distance(first, *el)
+            .checked_mul((right - 1 - left) as u128)
+            .map(|scaled| left + (scaled / T::distance(first, last)) as usize)
+            .unwrap_or(left + (right - left) / 2); // fallback to the middle on (very unlikely) overflow
+
+        probes += 1;
+        match sorted[guess].cmp(el) {
+            Ordering::Less => left = guess + 1,
+            Ordering::Greater => right = guess,
+            Ordering::Equal => return (Ok(guess), probes),
+        }
+    }
+
+    // safeguard: values are not evenly spread, continue with binary search
+    let result = binary_search_by(&sorted[left..right], |m| {
+        probes += 1;
+        m.cmp(el)
+    });
+    (result.map(|i| i + left).map_err(|i| i + left), probes)
+}
+
+#[test]
+fn interpolation_search_works() {
+    let a = [1, 2, 40, 61, 121, 333, 335, 380];
+    assert_eq!(Err(2), interpolation_search(&a, &4));
+    assert_eq!(Ok(1), interpolation_search(&a, &2));
+    assert_eq!(Ok(4), interpolation_search(&a, &121));
+    assert_eq!(Ok(0), interpolation_search(&a, &1));
+    assert_eq!(Ok(7), interpolation_search(&a, &380));
+    assert_eq!(Err(0), interpolation_search(&a, &0));
+    assert_eq!(Err(8), interpolation_search(&a, &400));
+    assert_eq!(Err(0), interpolation_search::<u8>(&[], &4));
+    assert_eq!(Ok(1), interpolation_search(&[i64::MIN, 0, i64::MAX], &0));
+    assert_eq!(
+        Ok(2),
+        interpolation_search(&[0, u64::MAX - 1, u64::MAX], &u64::MAX)
+    );
+
+    // extreme values of the widest types
+    let extremes = [i128::MIN, i128::MIN + 1, -1, 0, 1, i128::MAX - 1, i128::MAX];
+    for (i, el) in extremes.iter().enumerate() {
+        assert_eq!(Ok(i), interpolation_search(&extremes, el));
+    }
+    assert_eq!(Ok(1), interpolation_search(&[i128::MIN, 0, i128::MAX], &0));
+    assert_eq!(
+        Err(1),
+        interpolation_search(&[i128::MIN, 0, i128::MAX], &-5)
+    );
+    assert_eq!(Err(2), interpolation_search(&[i128::MIN, 0, i128::MAX], &5));
+    let unsigned = [0, 1, u128::MAX / 2, u128::MAX - 1, u128::MAX];
+    assert_eq!(Ok(2), interpolation_search(&unsigned, &(u128::MAX / 2)));
+    assert_eq!(
+        Err(3),
+        interpolation_search(&unsigned, &(u128::MAX / 2 + 1))
+    );
+    assert_eq!(Ok(4), interpolation_search(&unsigned, &u128::MAX));
+
+    // evenly spread ids (of index type) are found with few probes
+    let ids: Vec<usize> = (0..1_000_000).map(|i| i * 7).collect();
+    let (result, probes) = interpolation_search_with_probes(&ids, &(7 * 654_321));
+    assert_eq!(result, Ok(654_321));
+    assert!(probes <= 5, "probes: {probes}");
+
+    // skewed values fall back to binary search
+    let skewed: Vec<u64> = (0..64).map(|i| 1 << i).collect();
+    let (result, probes) = interpolation_search_with_probes(&skewed, &(1 << 40));
+    assert_eq!(result, Ok(40));
+    assert!(probes <= 3 * 7 + 7, "probes: {probes}");
+}
+
+#[test]
+fn interpolation_search_matches_binary_search() {
+    use crate::rng::XorShift64;
+    use crate::search::binary_search::{binary_search, equal_range};
+
+    let mut rng = XorShift64::new(28);
+    for _ in 0..300 {
+        let len = rng.index(50);
+        let mut a: Vec<i32> = (0..len).map(|_| rng.below(30) as i32 - 10).collect();
+        a.sort();
+        for el in -12..22 {
+            match (interpolation_search(&a, &el), binary_search(&a, &el)) {
+                (Ok(found), Ok(_)) => assert!(equal_range(&a, &el).contains(&found)),
+                (result, expected) => assert_eq!(result, expected, "{a:?} {el}"),
+            }
+        }
+    }
+}
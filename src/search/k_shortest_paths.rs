@@ -0,0 +1,297 @@
+use crate::data::graph::{Graph, GraphNodeIndex};
+use crate::data::visit::{GraphBase, Neighbors};
+use crate::data::weight::Weight;
+use crate::search::dijkstra::{
+    dijkstra_shortest_path, dijkstra_shortest_path_in_weighted_indexed_graph,
+};
+use crate::search::shortest_path::ShortestPath;
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashSet},
+};
+
+/// Loopless (simple) paths from start to target in order of cost, found lazily by Yen's algorithm.
+/// Paths are sequences of nodes, so paths differing only by parallel edges are not repeated
+/// (the cheapest of parallel edges is used).
+/// Use e.g. `.take(k)` to get k shortest paths.
+pub fn k_shortest_paths_in_weighted_indexed_graph<V, W: Weight>(
+    graph: &Graph<V, W>,
+    start: GraphNodeIndex,
+    target: GraphNodeIndex,
+) -> KShortestPaths<'_, V, W> {
+    KShortestPaths {
+        graph,
+        start,
+        target,
+        found: Vec::new(),
+        candidates: BinaryHeap::new(),
+        seen: HashSet::new(),
+    }
+}
+
+pub struct KShortestPaths<'a, V, W> {
+    graph: &'a Graph<V, W>,
+    start: GraphNodeIndex,
+    target: GraphNodeIndex,
+    /// Paths already returned, in order of cost.
+    found: Vec<ShortestPath<GraphNodeIndex, W>>,
+    /// Paths which are not returned yet, the cheapest on top.
+    candidates: BinaryHeap<Reverse<(W, Vec<GraphNodeIndex>)>>,
+    /// All paths which were ever added to candidates (so they are not added twice).
+    seen: HashSet<Vec<GraphNodeIndex>>,
+}
+
+impl<V, W: Weight> KShortestPaths<'_, V, W> {
+    /// Add candidates which share a prefix (root) with the last found path and then deviate from it.
+    fn add_deviations_of_last_found(&mut self) {
+        let last = &self.found[self.found.len() - 1].nodes;
+        let mut root_cost = W::zero();
+        // nodes of root (except spur node) are masked, so the whole path is loopless
+        let mut masked_nodes = vec![false; self.graph.nodes.len()];
+        for spur_index in 0..last.len() - 1 {
+            let root = &last[..=spur_index];
+            let spur_node = last[spur_index];
+            if spur_index > 0 {
+                masked_nodes[last[spur_index - 1]] = true;
+            }
+
+            // edges following the same root in already found paths are masked, so the spur path deviates
+            let masked_edges: HashSet<(GraphNodeIndex, GraphNodeIndex)> = self
+                .found
+                .iter()
+                .filter(|path| path.nodes.len() > spur_index + 1 && path.nodes.starts_with(root))
+                .map(|path| (spur_node, path.nodes[spur_index + 1]))
+                .collect();
+
+            let masked = Masked {
+                graph: self.graph,
+                nodes: &masked_nodes,
+                edges: &masked_edges,
+            };
+            if let Some(spur) = dijkstra_shortest_path(&masked, spur_node, self.target) {
+                if let Some(cost) = root_cost.checked_add(&spur.cost) {
+                    let mut nodes = root[..spur_index].to_vec();
+                    nodes.extend(spur.nodes);
+                    if self.seen.insert(nodes.clone()) {
+                        self.candidates.push(Reverse((cost, nodes)));
+                    }
+                }
+            }
+
+            let Some(cost) = cheapest_edge(self.graph, spur_node, last[spur_index + 1])
+                .and_then(|edge| root_cost.checked_add(edge))
+            else {
+                return; // overflow
+            };
+            root_cost = cost;
+        }
+    }
+}
+
+impl<V, W: Weight> Iterator for KShortestPaths<'_, V, W> {
+    type Item = ShortestPath<GraphNodeIndex, W>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.found.is_empty() {
+            let path = dijkstra_shortest_path_in_weighted_indexed_graph(
+                self.graph,
+                self.start,
+                self.target,
+            )?;
+            self.seen.insert(path.nodes.clone());
+            self.found.push(path.clone());
+            return Some(path);
+        }
+
+        self.add_deviations_of_last_found();
+        let Reverse((cost, nodes)) = self.candidates.pop()?;
+        let path = ShortestPath { nodes, cost };
+        self.found.push(path.clone());
+        Some(path)
+    }
+}
+
+/// Graph without masked nodes (with all their edges) and masked edges, masking does not copy the graph.
+struct Masked<'a, V, W> {
+    graph: &'a Graph<V, W>,
+    /// Index is GraphNodeIndex, true if node is masked.
+    nodes: &'a [bool],
+    edges: &'a HashSet<(GraphNodeIndex, GraphNodeIndex)>,
+}
+
+impl<V, W> GraphBase for Masked<'_, V, W> {
+    type NodeId = GraphNodeIndex;
+    type Edge = W;
+    type Map<T> = Vec<Option<T>>;
+
+    fn visit_map<T>(&self) -> Self::Map<T> {
+        self.graph.visit_map()
+    }
+
+    fn contains_node(&self, node: &GraphNodeIndex) -> bool {
+        !self.nodes[*node]
+    }
+}
+
+impl<V, W> Neighbors for Masked<'_, V, W> {
+    fn for_each_neighbour<F: FnMut(&GraphNodeIndex, &W)>(&self, node: &GraphNodeIndex, mut f: F) {
+        if self.nodes[*node] {
+            return;
+        }
+        self.graph.for_each_neighbour(node, |neighbour, edge| {
+            if !self.nodes[*neighbour] && !self.edges.contains(&(*node, *neighbour)) {
+                f(neighbour, edge);
+            }
+        });
+    }
+}
+
+/// Weight of the cheapest edge from -> to.
+fn cheapest_edge<V, W: Ord>(
+    graph: &Graph<V, W>,
+    from: GraphNodeIndex,
+    to: GraphNodeIndex,
+) -> Option<&W> {
+    graph.edges[from]
+        .iter()
+        .filter(|(neighbour, _)| *neighbour == to)
+        .map(|(_, weight)| weight)
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::graph::{
+        make_random_weighted_indexed_graph, make_test_weighted_indexed_graph,
+    };
+    use crate::rng::XorShift64;
+
+    /// Costs of all simple paths from start to target (found by brute force), in increasing order.
+    fn all_simple_path_costs(
+        graph: &Graph<impl Sized, u64>,
+        start: usize,
+        target: usize,
+    ) -> Vec<u64> {
+        fn visit(
+            graph: &Graph<impl Sized, u64>,
+            current: usize,
+            target: usize,
+            cost: u64,
+            on_path: &mut Vec<bool>,
+            costs: &mut Vec<u64>,
+        ) {
+            if current == target {
+                costs.push(cost);
+                return;
+            }
+            on_path[current] = true;
+            let mut neighbours: Vec<usize> = graph.edges[current].iter().map(|&(n, _)| n).collect();
+            neighbours.sort();
+            neighbours.dedup();
+            for neighbour in neighbours {
+                if !on_path[neighbour] {
+                    let edge = cheapest_edge(graph, current, neighbour).unwrap();
+                    visit(graph, neighbour, target, cost + edge, on_path, costs);
+                }
+            }
+            on_path[current] = false;
+        }
+        let mut costs = Vec::new();
+        visit(
+            graph,
+            start,
+            target,
+            0,
+            &mut vec![false; graph.nodes.len()],
+            &mut costs,
+        );
+        costs.sort();
+        costs
+    }
+
+    /// Paths are distinct, simple, follow edges from start to target and have correct costs.
+    fn assert_valid_paths(
+        graph: &Graph<impl Sized, u64>,
+        start: usize,
+        target: usize,
+        paths: &[ShortestPath<usize, u64>],
+    ) {
+        let mut distinct = HashSet::new();
+        for path in paths {
+            assert!(distinct.insert(path.nodes.clone()), "{path:?} repeated");
+            assert_eq!(
+                (path.nodes[0], *path.nodes.last().unwrap()),
+                (start, target)
+            );
+            let nodes: HashSet<_> = path.nodes.iter().collect();
+            assert_eq!(nodes.len(), path.nodes.len(), "{path:?} has a loop");
+            let cost: u64 = path
+                .nodes
+                .windows(2)
+                .map(|w| *cheapest_edge(graph, w[0], w[1]).unwrap())
+                .sum();
+            assert_eq!(cost, path.cost);
+        }
+    }
+
+    #[test]
+    fn k_shortest_paths_works() {
+        let graph = make_test_weighted_indexed_graph();
+        let paths: Vec<_> = k_shortest_paths_in_weighted_indexed_graph(&graph, 0, 5)
+            .take(4)
+            .collect();
+        assert_eq!(paths[0].nodes, [0, 4, 5]);
+        assert_eq!(paths[0].cost, 2);
+        // 0 -> 3 -> 4 -> 5
+        assert_eq!(paths[1].cost, 3);
+        assert_eq!(paths[1].nodes, [0, 3, 4, 5]);
+        // 0 -> 1 -> 6 -> 5 and 0 -> 2 -> 3 -> 4 -> 5 and ...
+        assert_eq!(paths[2].cost, 4);
+        assert_valid_paths(&graph, 0, 5, &paths);
+
+        // all simple paths, in order of cost
+        let paths: Vec<_> = k_shortest_paths_in_weighted_indexed_graph(&graph, 1, 7).collect();
+        assert_valid_paths(&graph, 1, 7, &paths);
+        assert_eq!(
+            paths.iter().map(|path| path.cost).collect::<Vec<_>>(),
+            all_simple_path_costs(&graph, 1, 7)
+        );
+
+        assert_eq!(
+            k_shortest_paths_in_weighted_indexed_graph(&graph, 3, 3).collect::<Vec<_>>(),
+            [ShortestPath {
+                nodes: vec![3],
+                cost: 0
+            }]
+        );
+    }
+
+    #[test]
+    fn k_shortest_paths_stops_when_target_is_not_reachable() {
+        let mut graph = make_test_weighted_indexed_graph();
+        graph.nodes.push('i');
+        graph.edges.push(vec![(0, 1)]);
+        assert_eq!(
+            k_shortest_paths_in_weighted_indexed_graph(&graph, 0, 8).next(),
+            None
+        );
+    }
+
+    #[test]
+    fn k_shortest_paths_matches_brute_force() {
+        let mut rng = XorShift64::new(39);
+        for _ in 0..50 {
+            let n = 2 + rng.index(7);
+            let graph = make_random_weighted_indexed_graph(&mut rng, n, 3 * n, 5);
+            let (start, target) = (rng.index(n), rng.index(n));
+            let paths: Vec<_> =
+                k_shortest_paths_in_weighted_indexed_graph(&graph, start, target).collect();
+            assert_valid_paths(&graph, start, target, &paths);
+            assert_eq!(
+                paths.iter().map(|path| path.cost).collect::<Vec<_>>(),
+                all_simple_path_costs(&graph, start, target)
+            );
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use crate::search::haystack::{EmptyPatternMatches, Haystack};
+
+/// Prefix function of pattern: prefix[i] is the length of the longest proper prefix of pattern[..=i],
+/// which is also a suffix of pattern[..=i].
+/// An example: "abacab" -> [0, 0, 1, 0, 1, 2]
+pub fn prefix_function(pattern: &[u8]) -> Vec<usize> {
+    let mut prefix = vec![0; pattern.len()];
+    for i in 1..pattern.len() {
+        // try to extend the longest border of pattern[..i], if it fails try shorter borders
+        let mut k = prefix[i - 1];
+        while k > 0 && pattern[i] != pattern[k] {
+            k = prefix[k - 1];
+        }
+        if pattern[i] == pattern[k] {
+            k += 1;
+        }
+        prefix[i] = k;
+    }
+    prefix
+}
+
+/// Iterator over all (also overlapping) match positions of pattern in haystack.
+pub struct KmpMatches<'a> {
+    haystack: &'a [u8],
+    pattern: &'a [u8],
+    // Some if pattern is empty
+    empty: Option<EmptyPatternMatches<'a>>,
+    prefix: Vec<usize>,
+    // next haystack index to check
+    position: usize,
+    // number of pattern bytes matched just before position
+    matched: usize,
+}
+
+/// Search for all positions (byte indexes) of pattern in haystack by using KMP in O(n + m).
+/// Works for both &str and &[u8]. Empty pattern matches at every char boundary of &str
+/// and at every position 0..=haystack.len() of &[u8].
+pub fn kmp_search<'a, H, P>(haystack: &'a H, pattern: &'a P) -> KmpMatches<'a>
+where
+    H: Haystack + ?Sized,
+    P: AsRef<[u8]> + ?Sized,
+{
+    let pattern = pattern.as_ref();
+    KmpMatches {
+        haystack: haystack.as_ref(),
+        pattern,
+        empty: EmptyPatternMatches::of_pattern(haystack, pattern),
+        prefix: prefix_function(pattern),
+        position: 0,
+        matched: 0,
+    }
+}
+
+impl Iterator for KmpMatches<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if let Some(empty) = &mut self.empty {
+            return empty.next();
+        }
+        let m = self.pattern.len();
+
+        while self.position < self.haystack.len() {
+            let byte = self.haystack[self.position];
+            self.position += 1;
+
+            while self.matched > 0 && self.pattern[self.matched] != byte {
+                self.matched = self.prefix[self.matched - 1];
+            }
+            if self.pattern[self.matched] == byte {
+                self.matched += 1;
+            }
+            if self.matched == m {
+                self.matched = self.prefix[m - 1]; // continue with the longest border, so overlapping matches are found
+                return Some(self.position - m);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::tests::test_pattern_search;
+
+    #[test]
+    fn prefix_function_works() {
+        assert_eq!(prefix_function(b"abacab"), [0, 0, 1, 0, 1, 2]);
+        assert_eq!(prefix_function(b"aaaa"), [0, 1, 2, 3]);
+        assert_eq!(prefix_function(b"abcabcd"), [0, 0, 0, 1, 2, 3, 0]);
+        assert_eq!(prefix_function(b""), [0; 0]);
+    }
+
+    #[test]
+    fn kmp_search_works() {
+        assert_eq!(
+            kmp_search("abracadabra", "abra").collect::<Vec<_>>(),
+            [0, 7]
+        );
+        assert_eq!(
+            kmp_search(b"aaaa".as_slice(), b"aa").collect::<Vec<_>>(),
+            [0, 1, 2]
+        );
+        test_pattern_search(|haystack, pattern| kmp_search(haystack, pattern).collect());
+    }
+}
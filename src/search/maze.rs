@@ -63,7 +63,7 @@ impl Position {
 }
 
 #[cfg(test)]
-use crate::data::graph::Graph;
+use crate::data::{graph::Graph, graph_builder::GraphBuilder};
 
 // Test maze to test graph:
 // '1' means wall
@@ -77,13 +77,10 @@ use crate::data::graph::Graph;
 //        10000010
 // start->00111000
 #[cfg(test)]
-fn construct_test_maze_as_graph() -> Graph<Position, ()> {
-    let mut graph = Graph {
-        nodes: Vec::new(),
-        edges: Vec::new(),
-    };
+pub(crate) fn construct_test_maze_as_graph() -> Graph<Position, ()> {
+    let mut builder = GraphBuilder::new();
     // '1' means wall
-    let maze = vec![
+    let maze = [
         vec![1, 0, 1, 0, 0, 0, 1, 0],
         vec![0, 0, 1, 0, 0, 1, 0, 0],
         vec![0, 1, 1, 0, 1, 1, 0, 1],
@@ -101,10 +98,8 @@ fn construct_test_maze_as_graph() -> Graph<Position, ()> {
             // node is Some only if it is '0', i.e. part of path
             // node is None if it is a wall
             let node_position = Position::new(i, j);
-            let node_index: GraphNodeIndex = graph.nodes.len(); // This is equivalent to node_position.index(width), due to the order of looping over indexes 'i' and 'j' and the fact that we also add disconnected nodes
+            let node_index: GraphNodeIndex = builder.add_node(node_position); // This is equivalent to node_position.index(width), due to the order of looping over indexes 'i' and 'j' and the fact that we also add disconnected nodes
             debug_assert!(node_index < 64, "node_position: {node_position:?}");
-            graph.nodes.push(node_position);
-            graph.edges.push(Vec::new());
 
             // walls are just disconnected nodes in graph
             if maze[node_position.i][node_position.j] == 1 {
@@ -116,31 +111,33 @@ fn construct_test_maze_as_graph() -> Graph<Position, ()> {
             if let Some(neighbour_position) = node_position.up() {
                 if maze[neighbour_position.i][neighbour_position.j] == 0 {
                     let neighbour_index = neighbour_position.index(width);
-                    graph.edges[node_index].push((neighbour_index, ()));
+                    builder.add_edge(node_index, neighbour_index, ());
                 }
             }
             if let Some(neighbour_position) = node_position.right(width) {
                 if maze[neighbour_position.i][neighbour_position.j] == 0 {
                     let neighbour_index = neighbour_position.index(width);
-                    graph.edges[node_index].push((neighbour_index, ()));
+                    builder.add_edge(node_index, neighbour_index, ());
                 }
             }
             if let Some(neighbour_position) = node_position.down(height) {
                 if maze[neighbour_position.i][neighbour_position.j] == 0 {
                     let neighbour_index = neighbour_position.index(width);
-                    graph.edges[node_index].push((neighbour_index, ()));
+                    builder.add_edge(node_index, neighbour_index, ());
                 }
             }
             if let Some(neighbour_position) = node_position.left() {
                 if maze[neighbour_position.i][neighbour_position.j] == 0 {
                     let neighbour_index = neighbour_position.index(width);
-                    graph.edges[node_index].push((neighbour_index, ()));
+                    builder.add_edge(node_index, neighbour_index, ());
                 }
             }
         }
     }
 
-    graph
+    builder
+        .build()
+        .expect("edges lead only to neighbours inside maze")
 }
 
 #[cfg(test)]
@@ -176,8 +173,10 @@ mod tests {
             &maze,
             start_position.index(maze_width),
             end_position.index(maze_width),
-        );
-        assert!(found_path.len() == 19);
+        )
+        .unwrap();
+        assert!(found_path.nodes.len() == 19);
+        assert_eq!(found_path.cost, 18);
 
         let expected_path = vec![
             Position { i: 7, j: 0 },
@@ -203,6 +202,7 @@ mod tests {
         assert_eq!(
             expected_path,
             found_path
+                .nodes
                 .into_iter()
                 .map(|index| Position::from_index(index, maze_width))
                 .collect::<Vec<Position>>()
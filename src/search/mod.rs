@@ -1,5 +1,105 @@
+pub mod aho_corasick;
+pub mod all_pairs;
+pub mod answer_search;
+pub mod astar;
+pub mod bellman_ford;
+pub mod bench;
 pub mod bfs;
+pub mod bidirectional;
 pub mod binary_search;
+pub mod boyer_moore_horspool;
+pub mod dag;
 pub mod dfs;
 pub mod dijkstra;
+pub mod exponential_search;
+pub mod fibonacci_search;
+pub mod haystack;
+pub mod interpolation_search;
+pub mod k_shortest_paths;
+pub mod kmp;
 pub mod maze;
+pub mod rabin_karp;
+pub mod shortest_path;
+pub mod suffix_array;
+pub mod z_algorithm;
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::rng::XorShift64;
+
+    /// All (also overlapping) positions of pattern in haystack, found by brute force.
+    fn all_matches(haystack: &str, pattern: &str) -> Vec<usize> {
+        (0..=haystack.len())
+            .filter(|&i| haystack.as_bytes()[i..].starts_with(pattern.as_bytes()))
+            .collect()
+    }
+
+    /// Leftmost non-overlapping subset of matches, i.e. what str::match_indices returns.
+    fn non_overlapping(matches: &[usize], pattern_len: usize) -> Vec<usize> {
+        let mut output: Vec<usize> = Vec::new();
+        for &i in matches {
+            if output.last().is_none_or(|&last| last + pattern_len <= i) {
+                output.push(i);
+            }
+        }
+        output
+    }
+
+    /// Cross-check substring search (returning all match positions) against str::match_indices.
+    pub(crate) fn test_pattern_search<F>(find_all: F)
+    where
+        F: Fn(&str, &str) -> Vec<usize>,
+    {
+        let mut cases = vec![
+            ("abracadabra".to_string(), "abra".to_string()),
+            ("abracadabra".to_string(), "a".to_string()),
+            ("abracadabra".to_string(), "abracadabra".to_string()),
+            ("abracadabra".to_string(), "abracadabrab".to_string()),
+            ("aaaaaa".to_string(), "aaa".to_string()),
+            ("abababab".to_string(), "abab".to_string()),
+            ("".to_string(), "a".to_string()),
+            ("zażółć gęślą jaźń".to_string(), "ź".to_string()),
+            ("zażółć gęślą jaźń".to_string(), "ślą".to_string()),
+        ];
+        // random strings over small alphabet, so there are many (overlapping) matches
+        let mut rng = XorShift64::new(30);
+        for _ in 0..300 {
+            let random_string = |rng: &mut XorShift64, len: usize| -> String {
+                (0..len)
+                    .map(|_| (b'a' + rng.below(3) as u8) as char)
+                    .collect()
+            };
+            let haystack_len = rng.index(60);
+            let pattern_len = 1 + rng.index(5);
+            let haystack = random_string(&mut rng, haystack_len);
+            let pattern = random_string(&mut rng, pattern_len);
+            cases.push((haystack, pattern));
+        }
+
+        for (haystack, pattern) in &cases {
+            let found = find_all(haystack, pattern);
+            assert_eq!(
+                found,
+                all_matches(haystack, pattern),
+                "{haystack:?} {pattern:?}"
+            );
+
+            let std_matches: Vec<usize> = haystack
+                .match_indices(pattern.as_str())
+                .map(|(i, _)| i)
+                .collect();
+            assert_eq!(
+                non_overlapping(&found, pattern.len()),
+                std_matches,
+                "{haystack:?} {pattern:?}"
+            );
+        }
+
+        // empty pattern matches everywhere, in str only at char boundaries
+        assert_eq!(find_all("abc", ""), [0, 1, 2, 3]);
+        assert_eq!(find_all("", ""), [0]);
+        let text = "zażółć gęślą";
+        let std_matches: Vec<usize> = text.match_indices("").map(|(i, _)| i).collect();
+        assert_eq!(find_all(text, ""), std_matches);
+    }
+}
@@ -0,0 +1,109 @@
+use crate::search::haystack::{EmptyPatternMatches, Haystack};
+
+// hash(s) = s[0] * BASE^(m-1) + s[1] * BASE^(m-2) + ... + s[m-1]  (mod MODULUS)
+const MODULUS: u64 = (1 << 61) - 1; // Mersenne prime, so collisions are very unlikely
+const BASE: u64 = 257;
+
+fn mul_mod(a: u64, b: u64) -> u64 {
+    ((a as u128 * b as u128) % MODULUS as u128) as u64
+}
+
+fn hash(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(0, |h, &byte| (mul_mod(h, BASE) + byte as u64) % MODULUS)
+}
+
+/// Iterator over all (also overlapping) match positions of pattern in haystack.
+pub struct RabinKarpMatches<'a> {
+    haystack: &'a [u8],
+    pattern: &'a [u8],
+    // Some if pattern is empty
+    empty: Option<EmptyPatternMatches<'a>>,
+    pattern_hash: u64,
+    // hash of haystack[position..position + m]
+    window_hash: u64,
+    // BASE^(m-1), used to remove the first byte of window from hash
+    highest_power: u64,
+    position: usize,
+}
+
+/// Search for all positions (byte indexes) of pattern in haystack by using Rabin–Karp.
+/// Hash of window is updated in O(1) when window moves, bytes are compared only when hashes are equal.
+/// Works for both &str and &[u8]. Empty pattern matches at every char boundary of &str
+/// and at every position 0..=haystack.len() of &[u8].
+pub fn rabin_karp_search<'a, H, P>(haystack: &'a H, pattern: &'a P) -> RabinKarpMatches<'a>
+where
+    H: Haystack + ?Sized,
+    P: AsRef<[u8]> + ?Sized,
+{
+    let pattern = pattern.as_ref();
+    let empty = EmptyPatternMatches::of_pattern(haystack, pattern);
+    let haystack = haystack.as_ref();
+    let m = pattern.len();
+
+    let highest_power = (1..m).fold(1, |power, _| mul_mod(power, BASE));
+    let window_hash = if m <= haystack.len() {
+        hash(&haystack[..m])
+    } else {
+        0
+    };
+
+    RabinKarpMatches {
+        haystack,
+        pattern,
+        empty,
+        pattern_hash: hash(pattern),
+        window_hash,
+        highest_power,
+        position: 0,
+    }
+}
+
+impl Iterator for RabinKarpMatches<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if let Some(empty) = &mut self.empty {
+            return empty.next();
+        }
+        let m = self.pattern.len();
+        while self.position + m <= self.haystack.len() {
+            let start = self.position;
+            let is_match = self.window_hash == self.pattern_hash
+                && &self.haystack[start..start + m] == self.pattern;
+
+            // roll the hash: remove haystack[start], add haystack[start + m]
+            self.position += 1;
+            if start + m < self.haystack.len() {
+                let removed = mul_mod(self.haystack[start] as u64, self.highest_power);
+                let h = (self.window_hash + MODULUS - removed) % MODULUS;
+                self.window_hash = (mul_mod(h, BASE) + self.haystack[start + m] as u64) % MODULUS;
+            }
+
+            if is_match {
+                return Some(start);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::tests::test_pattern_search;
+
+    #[test]
+    fn rabin_karp_search_works() {
+        assert_eq!(
+            rabin_karp_search("abracadabra", "abra").collect::<Vec<_>>(),
+            [0, 7]
+        );
+        assert_eq!(
+            rabin_karp_search(b"aaaa".as_slice(), b"aa").collect::<Vec<_>>(),
+            [0, 1, 2]
+        );
+        test_pattern_search(|haystack, pattern| rabin_karp_search(haystack, pattern).collect());
+    }
+}
@@ -0,0 +1,133 @@
+use crate::data::graph::GraphNodeIndex;
+use crate::data::visit::VisitMap;
+
+/// Path found by shortest path search.
+/// - N is node type (e.g. GraphNodeIndex or Rc<GraphNode>)
+/// - W is cost type (number of edges for unweighted graphs, sum of weights for weighted graphs)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShortestPath<N, W> {
+    /// Nodes of path from start to target (both included).
+    pub nodes: Vec<N>,
+    pub cost: W,
+}
+
+/// Result of single-source shortest path search in indexed graph.
+/// Index of 'distances' and 'previous' is GraphNodeIndex.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShortestPathTree<W> {
+    pub start: GraphNodeIndex,
+    /// Cost of the shortest path from start, None if node is not reachable.
+    pub distances: Vec<Option<W>>,
+    /// Predecessor of node on the shortest path from start, None for start and not reachable nodes.
+    pub previous: Vec<Option<GraphNodeIndex>>,
+}
+
+impl<W: Clone> ShortestPathTree<W> {
+    /// Shortest path from start to target, None if target is not reachable.
+    pub fn path(&self, target: GraphNodeIndex) -> Option<ShortestPath<GraphNodeIndex, W>> {
+        let cost = self.distances[target].clone()?;
+        Some(ShortestPath {
+            nodes: reconstruct_path(&self.previous, target),
+            cost,
+        })
+    }
+}
+
+/// Path from the root of predecessor tree to target, by following 'previous' links back from target.
+pub fn reconstruct_path(
+    previous: &[Option<GraphNodeIndex>],
+    target: GraphNodeIndex,
+) -> Vec<GraphNodeIndex> {
+    let mut path = vec![target];
+    let mut current = target;
+    while let Some(parent) = previous[current] {
+        path.push(parent);
+        current = parent;
+    }
+    path.reverse();
+    path
+}
+
+/// Path from the root of predecessor map to target (like reconstruct_path), for graph of any representation.
+pub(crate) fn reconstruct_path_in_map<N: Clone, M: VisitMap<N, N>>(
+    previous: &M,
+    target: N,
+) -> Vec<N> {
+    let mut path = vec![target];
+    while let Some(parent) = previous.get(&path[path.len() - 1]) {
+        path.push(parent.clone());
+    }
+    path.reverse();
+    path
+}
+
+/// Cycle with negative total cost, found by algorithms which allow negative edge weights.
+/// Shortest paths through such cycle do not exist (cost can be decreased infinitely).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NegativeCycle {
+    /// Nodes of cycle in edge direction: there are edges nodes[i] -> nodes[i + 1]
+    /// and closing edge nodes[last] -> nodes[0] (first node is not repeated at the end).
+    pub nodes: Vec<GraphNodeIndex>,
+}
+
+/// Error of shortest path algorithms which allow negative edge weights.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NegativeWeightsError {
+    /// Negative cycle, so the shortest paths do not exist.
+    NegativeCycle(NegativeCycle),
+    /// Cost which is needed to find the shortest paths does not fit in weight type: the cheapest path
+    /// to node is below the minimum (negative overflow), or Johnson's reweighted edge to node is above the maximum.
+    Overflow { node: GraphNodeIndex },
+}
+
+/// Cycle of predecessor links reached by following 'previous' back from node 'from',
+/// None if root of predecessor tree is reached instead.
+pub(crate) fn predecessor_cycle(
+    previous: &[Option<GraphNodeIndex>],
+    from: GraphNodeIndex,
+) -> Option<NegativeCycle> {
+    // after n steps back we are surely on the cycle (if there is one)
+    let mut current = from;
+    for _ in 0..previous.len() {
+        current = previous[current]?;
+    }
+    let mut nodes = vec![current];
+    let mut node = previous[current]?;
+    while node != current {
+        nodes.push(node);
+        node = previous[node]?;
+    }
+    // predecessors go against edge direction
+    nodes.reverse();
+    Some(NegativeCycle { nodes })
+}
+
+/// Result of all-pairs shortest path search in indexed graph.
+/// Both indices of 'distances' and 'previous' are GraphNodeIndex: [from][to].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DistanceMatrix<W> {
+    /// Cost of the shortest path from -> to, None if 'to' is not reachable from 'from'.
+    pub distances: Vec<Vec<Option<W>>>,
+    /// previous[from] is predecessor array of the shortest paths from 'from' (as in ShortestPathTree).
+    pub previous: Vec<Vec<Option<GraphNodeIndex>>>,
+}
+
+impl<W: Clone> DistanceMatrix<W> {
+    /// Cost of the shortest path from u to v, None if v is not reachable from u.
+    pub fn distance(&self, u: GraphNodeIndex, v: GraphNodeIndex) -> Option<&W> {
+        self.distances[u][v].as_ref()
+    }
+
+    /// Shortest path from u to v, None if v is not reachable from u.
+    pub fn path(
+        &self,
+        u: GraphNodeIndex,
+        v: GraphNodeIndex,
+    ) -> Option<ShortestPath<GraphNodeIndex, W>> {
+        let cost = self.distances[u][v].clone()?;
+        Some(ShortestPath {
+            nodes: reconstruct_path(&self.previous[u], v),
+            cost,
+        })
+    }
+}
@@ -0,0 +1,192 @@
+use crate::search::binary_search::equal_range_by;
+use crate::sort::{mergesort::MergeSort, Sorter};
+use std::ops::Range;
+
+/// Suffix array of text: starting positions of all suffixes of text, in lexicographic order of suffixes.
+/// Built by prefix doubling: in round k suffixes are sorted by their first 2^k bytes,
+/// using (rank of first half, rank of second half) as a key, in O(n log^2 n).
+pub fn suffix_array(text: &[u8]) -> Vec<usize> {
+    let n = text.len();
+    let mut suffixes: Vec<usize> = (0..n).collect();
+    if n < 2 {
+        return suffixes;
+    }
+    // rank of suffix i by its first k bytes
+    let mut rank: Vec<usize> = text.iter().map(|&byte| byte as usize).collect();
+    let mut k = 1;
+    loop {
+        // key of suffix i by its first 2k bytes, 0 means "shorter than 2k" (i.e. the smallest)
+        let key = |i: usize| (rank[i] + 1, rank.get(i + k).map_or(0, |r| r + 1));
+        let mut keyed: Vec<((usize, usize), usize)> = (0..n).map(|i| (key(i), i)).collect();
+        MergeSort::sort(&mut keyed);
+
+        let mut new_rank = vec![0; n];
+        for w in 1..n {
+            let ((previous_key, previous), (current_key, current)) = (keyed[w - 1], keyed[w]);
+            new_rank[current] = new_rank[previous] + usize::from(previous_key != current_key);
+        }
+        suffixes = keyed.into_iter().map(|(_, i)| i).collect();
+        rank = new_rank;
+
+        // all ranks are distinct, so suffixes are sorted
+        if rank[suffixes[n - 1]] == n - 1 {
+            break;
+        }
+        k *= 2;
+    }
+    suffixes
+}
+
+/// LCP array (Kasai's algorithm) in O(n):
+/// lcp[i] is the length of the longest common prefix of suffixes[i - 1] and suffixes[i], lcp[0] = 0.
+pub fn lcp_array(text: &[u8], suffixes: &[usize]) -> Vec<usize> {
+    let n = text.len();
+    let mut rank = vec![0; n];
+    for (i, &suffix) in suffixes.iter().enumerate() {
+        rank[suffix] = i;
+    }
+
+    let mut lcp = vec![0; n];
+    // going from the longest suffix, lcp decreases by at most 1 when the first byte is removed
+    let mut h: usize = 0;
+    for suffix in 0..n {
+        if rank[suffix] == 0 {
+            h = 0;
+            continue;
+        }
+        let previous = suffixes[rank[suffix] - 1];
+        while suffix + h < n && previous + h < n && text[suffix + h] == text[previous + h] {
+            h += 1;
+        }
+        lcp[rank[suffix]] = h;
+        h = h.saturating_sub(1);
+    }
+    lcp
+}
+
+/// Suffix array with LCP array for substring queries over fixed text.
+pub struct SuffixArray<'a> {
+    text: &'a [u8],
+    suffixes: Vec<usize>,
+    lcp: Vec<usize>,
+}
+
+impl<'a> SuffixArray<'a> {
+    /// Build suffix array of text (works for both &str and &[u8]).
+    pub fn new<T: AsRef<[u8]> + ?Sized>(text: &'a T) -> Self {
+        let text = text.as_ref();
+        let suffixes = suffix_array(text);
+        let lcp = lcp_array(text, &suffixes);
+        SuffixArray {
+            text,
+            suffixes,
+            lcp,
+        }
+    }
+
+    pub fn suffixes(&self) -> &[usize] {
+        &self.suffixes
+    }
+
+    pub fn lcp(&self) -> &[usize] {
+        &self.lcp
+    }
+
+    /// Range of suffix array with suffixes starting with pattern (found by binary search).
+    fn pattern_range(&self, pattern: &[u8]) -> Range<usize> {
+        equal_range_by(&self.suffixes, |&suffix| {
+            // compare only first pattern.len() bytes of suffix, so all suffixes starting with pattern are 'Equal'
+            let end = (suffix + pattern.len()).min(self.text.len());
+            self.text[suffix..end].cmp(pattern)
+        })
+    }
+
+    /// Number of (also overlapping) occurrences of pattern in text, in O(m log n).
+    pub fn count<P: AsRef<[u8]> + ?Sized>(&self, pattern: &P) -> usize {
+        self.pattern_range(pattern.as_ref()).len()
+    }
+
+    /// Positions of all (also overlapping) occurrences of pattern in text, in increasing order.
+    pub fn locate<P: AsRef<[u8]> + ?Sized>(&self, pattern: &P) -> Vec<usize> {
+        let mut positions = self.suffixes[self.pattern_range(pattern.as_ref())].to_vec();
+        positions.sort();
+        positions
+    }
+
+    /// The longest substring which occurs at least twice in text (occurrences can overlap),
+    /// as a range of text, or None if no byte repeats.
+    /// If there are more such substrings, the lexicographically smallest one is returned.
+    pub fn longest_repeated_substring(&self) -> Option<Range<usize>> {
+        // the longest repeated substring is the longest common prefix of some neighbouring suffixes
+        let (i, &len) = self
+            .lcp
+            .iter()
+            .enumerate()
+            .rev()
+            .max_by_key(|&(_, &len)| len)?;
+        (len > 0).then(|| self.suffixes[i]..self.suffixes[i] + len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::XorShift64;
+    use crate::search::kmp::kmp_search;
+
+    #[test]
+    fn suffix_array_works() {
+        // banana suffixes: a, ana, anana, banana, na, nana
+        let sa = SuffixArray::new("banana");
+        assert_eq!(sa.suffixes(), [5, 3, 1, 0, 4, 2]);
+        assert_eq!(sa.lcp(), [0, 1, 3, 0, 0, 2]);
+        assert_eq!(sa.count("ana"), 2);
+        assert_eq!(sa.locate("ana"), [1, 3]);
+        assert_eq!(sa.locate("a"), [1, 3, 5]);
+        assert_eq!(sa.count("nab"), 0);
+        assert_eq!(sa.count("bananas"), 0);
+        assert_eq!(sa.longest_repeated_substring(), Some(1..4));
+
+        assert_eq!(SuffixArray::new("abc").longest_repeated_substring(), None);
+        assert_eq!(SuffixArray::new("").suffixes(), [0; 0]);
+        assert_eq!(SuffixArray::new("").count("a"), 0);
+    }
+
+    #[test]
+    fn suffix_array_matches_naive() {
+        let mut rng = XorShift64::new(32);
+        for _ in 0..200 {
+            let len = rng.index(60);
+            let text: Vec<u8> = (0..len).map(|_| b'a' + rng.below(3) as u8).collect();
+            let sa = SuffixArray::new(&text);
+
+            let mut naive: Vec<usize> = (0..len).collect();
+            naive.sort_by_key(|&i| &text[i..]);
+            assert_eq!(sa.suffixes(), naive);
+
+            for i in 1..len {
+                let (a, b) = (&text[naive[i - 1]..], &text[naive[i]..]);
+                let common = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+                assert_eq!(sa.lcp()[i], common);
+            }
+
+            let pattern_len = 1 + rng.index(3);
+            let pattern: Vec<u8> = (0..pattern_len)
+                .map(|_| b'a' + rng.below(3) as u8)
+                .collect();
+            assert_eq!(
+                sa.locate(&pattern),
+                kmp_search(&text, &pattern).collect::<Vec<_>>()
+            );
+
+            // longest repeated substring occurs at least twice and nothing longer does
+            let longest = sa.longest_repeated_substring().map_or(0, |r| {
+                assert!(sa.count(&text[r.clone()]) >= 2);
+                r.len()
+            });
+            let repeats =
+                |l: usize| (0..len.saturating_sub(l)).any(|i| sa.count(&text[i..i + l + 1]) >= 2);
+            assert!(!repeats(longest), "{text:?}");
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use crate::search::haystack::{EmptyPatternMatches, Haystack};
+
+/// Z-function: z[i] is the length of the longest common prefix of s and s[i..].
+/// By convention z[0] = s.len().
+/// An example: "aabxaab" -> [7, 1, 0, 0, 3, 1, 0]
+pub fn z_function(s: &[u8]) -> Vec<usize> {
+    let n = s.len();
+    let mut z = vec![0; n];
+    if n == 0 {
+        return z;
+    }
+    z[0] = n;
+    // [left, right) is the rightmost found z-box, i.e. s[left..right] == s[..right - left]
+    let (mut left, mut right) = (0, 0);
+    for i in 1..n {
+        if i < right {
+            z[i] = (right - i).min(z[i - left]);
+        }
+        while i + z[i] < n && s[z[i]] == s[i + z[i]] {
+            z[i] += 1;
+        }
+        if i + z[i] > right {
+            (left, right) = (i, i + z[i]);
+        }
+    }
+    z
+}
+
+/// Iterator over all (also overlapping) match positions of pattern in haystack.
+/// Computes Z-function of (pattern + separator + haystack) lazily,
+/// keeping only Z-function of pattern in memory.
+pub struct ZMatches<'a> {
+    haystack: &'a [u8],
+    pattern: &'a [u8],
+    // Some if pattern is empty
+    empty: Option<EmptyPatternMatches<'a>>,
+    pattern_z: Vec<usize>,
+    position: usize,
+    // [left, right) is the rightmost z-box in haystack, i.e. haystack[left..right] == pattern[..right - left]
+    left: usize,
+    right: usize,
+}
+
+/// Search for all positions (byte indexes) of pattern in haystack by using Z-algorithm in O(n + m).
+/// Works for both &str and &[u8]. Empty pattern matches at every char boundary of &str
+/// and at every position 0..=haystack.len() of &[u8].
+pub fn z_search<'a, H, P>(haystack: &'a H, pattern: &'a P) -> ZMatches<'a>
+where
+    H: Haystack + ?Sized,
+    P: AsRef<[u8]> + ?Sized,
+{
+    let pattern = pattern.as_ref();
+    ZMatches {
+        haystack: haystack.as_ref(),
+        pattern,
+        empty: EmptyPatternMatches::of_pattern(haystack, pattern),
+        pattern_z: z_function(pattern),
+        position: 0,
+        left: 0,
+        right: 0,
+    }
+}
+
+impl Iterator for ZMatches<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if let Some(empty) = &mut self.empty {
+            return empty.next();
+        }
+        let m = self.pattern.len();
+
+        while self.position < self.haystack.len() {
+            let i = self.position;
+            self.position += 1;
+
+            // z-box is never longer than pattern (because of separator), so i - left < m
+            let mut z = 0;
+            if i < self.right {
+                z = (self.right - i).min(self.pattern_z[i - self.left]);
+            }
+            while z < m && i + z < self.haystack.len() && self.haystack[i + z] == self.pattern[z] {
+                z += 1;
+            }
+            if i + z > self.right {
+                (self.left, self.right) = (i, i + z);
+            }
+            if z == m {
+                return Some(i);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::tests::test_pattern_search;
+
+    #[test]
+    fn z_function_works() {
+        assert_eq!(z_function(b"aabxaab"), [7, 1, 0, 0, 3, 1, 0]);
+        assert_eq!(z_function(b"aaaaa"), [5, 4, 3, 2, 1]);
+        assert_eq!(z_function(b""), [0; 0]);
+    }
+
+    #[test]
+    fn z_search_works() {
+        assert_eq!(z_search("abracadabra", "abra").collect::<Vec<_>>(), [0, 7]);
+        assert_eq!(
+            z_search(b"aaaa".as_slice(), b"aa").collect::<Vec<_>>(),
+            [0, 1, 2]
+        );
+        test_pattern_search(|haystack, pattern| z_search(haystack, pattern).collect());
+    }
+}
@@ -0,0 +1,346 @@
+//! Benchmarking of registered sorters (used by `sortbench` binary).
+
+use crate::bench::{format_rows, percentile, Column, Format};
+use crate::rng::XorShift64;
+use crate::sort::{
+    bubblesort::{BubbleSort, BubbleSortDescending, BubbleSortWithSinkAtTheBeginning},
+    heapsort::HeapSort,
+    insertionsort::{
+        InsertionSortWithCustomBinarySearch, InsertionSortWithIndividualSwaps,
+        InsertionSortWithStdPartitionPointAndRotation,
+    },
+    mergesort::MergeSort,
+    quicksort::QuickSort,
+    selectionsort::SelectionSort,
+    Sorter,
+};
+use std::{
+    cell::Cell,
+    cmp::Ordering,
+    time::{Duration, Instant},
+};
+
+thread_local! {
+    static COMPARISONS: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Element wrapper which counts every comparison made by the sorter.
+#[derive(Clone, Debug)]
+pub struct Counted<T>(pub T);
+
+impl<T: PartialEq> PartialEq for Counted<T> {
+    fn eq(&self, other: &Self) -> bool {
+        COMPARISONS.with(|c| c.set(c.get() + 1));
+        self.0 == other.0
+    }
+}
+impl<T: Eq> Eq for Counted<T> {}
+impl<T: Ord> PartialOrd for Counted<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<T: Ord> Ord for Counted<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        COMPARISONS.with(|c| c.set(c.get() + 1));
+        self.0.cmp(&other.0)
+    }
+}
+
+fn reset_comparisons() {
+    COMPARISONS.with(|c| c.set(0));
+}
+
+fn comparisons() -> u64 {
+    COMPARISONS.with(|c| c.get())
+}
+
+pub type SortFn<T> = fn(&mut [T]);
+
+/// Sorter available for benchmarking: the same sort for plain data (timed)
+/// and for Counted data (comparisons are counted in a separate, not timed run).
+pub struct RegisteredSorter {
+    pub name: &'static str,
+    pub sort: SortFn<u64>,
+    pub sort_counted: SortFn<Counted<u64>>,
+}
+
+impl RegisteredSorter {
+    fn of<S: Sorter>(name: &'static str) -> Self {
+        RegisteredSorter {
+            name,
+            sort: S::sort,
+            sort_counted: S::sort,
+        }
+    }
+}
+
+/// All sorters from `sort` module (plus std sort as a reference).
+pub fn registered_sorters() -> Vec<RegisteredSorter> {
+    vec![
+        RegisteredSorter::of::<BubbleSort>("bubble"),
+        RegisteredSorter::of::<BubbleSortWithSinkAtTheBeginning>("bubble_sink_at_beginning"),
+        RegisteredSorter::of::<BubbleSortDescending>("bubble_descending"),
+        RegisteredSorter::of::<SelectionSort>("selection"),
+        RegisteredSorter::of::<HeapSort>("heap"),
+        RegisteredSorter::of::<InsertionSortWithIndividualSwaps>("insertion_swaps"),
+        RegisteredSorter::of::<InsertionSortWithStdPartitionPointAndRotation>(
+            "insertion_partition_point",
+        ),
+        RegisteredSorter::of::<InsertionSortWithCustomBinarySearch>("insertion_binary_search"),
+        RegisteredSorter::of::<MergeSort>("merge"),
+        RegisteredSorter::of::<QuickSort>("quick"),
+        RegisteredSorter {
+            name: "std",
+            sort: |slice| slice.sort(),
+            sort_counted: |slice| slice.sort(),
+        },
+    ]
+}
+
+/// Shape of the input data.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Distribution {
+    Random,
+    Sorted,
+    Reversed,
+    /// Sorted with ~1% of random swaps.
+    NearlySorted,
+    /// Random values from a small set (many duplicates).
+    FewUnique,
+}
+
+impl Distribution {
+    pub const ALL: [Distribution; 5] = [
+        Distribution::Random,
+        Distribution::Sorted,
+        Distribution::Reversed,
+        Distribution::NearlySorted,
+        Distribution::FewUnique,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Distribution::Random => "random",
+            Distribution::Sorted => "sorted",
+            Distribution::Reversed => "reversed",
+            Distribution::NearlySorted => "nearly_sorted",
+            Distribution::FewUnique => "few_unique",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Distribution> {
+        Distribution::ALL.into_iter().find(|d| d.name() == name)
+    }
+
+    /// Generate input of given size.
+    pub fn generate(self, size: usize, rng: &mut XorShift64) -> Vec<u64> {
+        match self {
+            Distribution::Random => (0..size).map(|_| rng.next_u64()).collect(),
+            Distribution::Sorted => (0..size as u64).collect(),
+            Distribution::Reversed => (0..size as u64).rev().collect(),
+            Distribution::NearlySorted => {
+                let mut v: Vec<u64> = (0..size as u64).collect();
+                for _ in 0..size / 100 {
+                    let (a, b) = (rng.index(size), rng.index(size));
+                    v.swap(a, b);
+                }
+                v
+            }
+            Distribution::FewUnique => (0..size).map(|_| rng.below(8)).collect(),
+        }
+    }
+}
+
+/// Benchmark configuration.
+#[derive(Clone, Debug)]
+pub struct BenchConfig {
+    pub sizes: Vec<usize>,
+    pub distributions: Vec<Distribution>,
+    /// Names of sorters to run, empty means all registered sorters.
+    pub sorters: Vec<String>,
+    /// Not measured runs before the measured ones.
+    pub warmups: usize,
+    /// Measured runs (at least 1).
+    pub runs: usize,
+    pub seed: u64,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        BenchConfig {
+            sizes: vec![100, 1000],
+            distributions: Distribution::ALL.to_vec(),
+            sorters: Vec::new(),
+            warmups: 1,
+            runs: 5,
+            seed: 42,
+        }
+    }
+}
+
+/// Result of benchmarking one sorter on one input.
+#[derive(Clone, Debug)]
+pub struct BenchResult {
+    pub sorter: &'static str,
+    pub distribution: Distribution,
+    pub size: usize,
+    pub median: Duration,
+    pub p95: Duration,
+    pub comparisons: u64,
+}
+
+/// Run all (selected) sorters over all sizes and distributions.
+/// Every sorter gets exactly the same input for given size and distribution.
+/// Returns Err with the name of the sorter if it is not registered.
+pub fn run(config: &BenchConfig) -> Result<Vec<BenchResult>, String> {
+    assert!(config.runs > 0, "at least 1 measured run is needed");
+    let all = registered_sorters();
+    if let Some(unknown) = config
+        .sorters
+        .iter()
+        .find(|name| !all.iter().any(|s| s.name == name.as_str()))
+    {
+        return Err(unknown.clone());
+    }
+    let selected: Vec<&RegisteredSorter> = all
+        .iter()
+        .filter(|s| config.sorters.is_empty() || config.sorters.iter().any(|n| n == s.name))
+        .collect();
+
+    let mut results = Vec::new();
+    for &distribution in &config.distributions {
+        for &size in &config.sizes {
+            let mut rng = XorShift64::new(config.seed);
+            let input = distribution.generate(size, &mut rng);
+
+            for sorter in &selected {
+                results.push(bench_one(sorter, distribution, &input, config));
+            }
+        }
+    }
+    Ok(results)
+}
+
+fn bench_one(
+    sorter: &RegisteredSorter,
+    distribution: Distribution,
+    input: &[u64],
+    config: &BenchConfig,
+) -> BenchResult {
+    for _ in 0..config.warmups {
+        let mut data = input.to_vec();
+        (sorter.sort)(&mut data);
+    }
+
+    let mut times = Vec::with_capacity(config.runs);
+    for _ in 0..config.runs {
+        let mut data = input.to_vec(); // cloning is not measured
+        let start = Instant::now();
+        (sorter.sort)(&mut data);
+        times.push(start.elapsed());
+        debug_assert!(data.windows(2).all(|w| w[0] <= w[1]), "{}", sorter.name);
+    }
+    times.sort();
+
+    // counting is not measured, the count is the same for every run (deterministic sorters)
+    let mut counted: Vec<Counted<u64>> = input.iter().copied().map(Counted).collect();
+    reset_comparisons();
+    (sorter.sort_counted)(&mut counted);
+    let comparison_count = comparisons();
+
+    BenchResult {
+        sorter: sorter.name,
+        distribution,
+        size: input.len(),
+        median: percentile(&times, 50),
+        p95: percentile(&times, 95),
+        comparisons: comparison_count,
+    }
+}
+
+/// Columns of results, in order of values returned by `row`.
+const COLUMNS: [Column; 6] = [
+    Column::text("sorter", 26),
+    Column::text("distribution", 14),
+    Column::number("size", 10),
+    Column::number("median_ns", 14),
+    Column::number("p95_ns", 14),
+    Column::number("comparisons", 14),
+];
+
+fn row(r: &BenchResult) -> Vec<String> {
+    vec![
+        r.sorter.to_string(),
+        r.distribution.name().to_string(),
+        r.size.to_string(),
+        r.median.as_nanos().to_string(),
+        r.p95.as_nanos().to_string(),
+        r.comparisons.to_string(),
+    ]
+}
+
+pub fn format_results(results: &[BenchResult], format: Format) -> String {
+    let rows: Vec<Vec<String>> = results.iter().map(row).collect();
+    format_rows(&COLUMNS, &rows, format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registered_sorters_sort_and_count_comparisons() {
+        let mut rng = XorShift64::new(7);
+        let input = Distribution::Random.generate(200, &mut rng);
+        let mut expected = input.clone();
+        expected.sort();
+
+        for sorter in registered_sorters() {
+            let mut counted: Vec<Counted<u64>> = input.iter().copied().map(Counted).collect();
+            reset_comparisons();
+            (sorter.sort_counted)(&mut counted);
+            assert!(comparisons() > 0, "{}", sorter.name);
+            assert_eq!(
+                counted.into_iter().map(|c| c.0).collect::<Vec<_>>(),
+                expected,
+                "{}",
+                sorter.name
+            );
+
+            // plain data is not counted
+            let mut data = input.clone();
+            reset_comparisons();
+            (sorter.sort)(&mut data);
+            assert_eq!(data, expected, "{}", sorter.name);
+            assert_eq!(comparisons(), 0, "{}", sorter.name);
+        }
+    }
+
+    #[test]
+    fn run_and_format_works() {
+        let config = BenchConfig {
+            sizes: vec![10, 20],
+            distributions: vec![Distribution::Sorted, Distribution::FewUnique],
+            sorters: vec!["quick".to_string(), "merge".to_string()],
+            warmups: 0,
+            runs: 3,
+            seed: 1,
+        };
+        let results = run(&config).unwrap();
+        assert_eq!(results.len(), 2 * 2 * 2);
+        assert!(results.iter().all(|r| r.median <= r.p95));
+
+        let csv = format_results(&results, Format::Csv);
+        assert_eq!(csv.lines().count(), 1 + results.len());
+        assert!(csv.contains("quick,sorted,10,"));
+        let json = format_results(&results, Format::Json);
+        assert!(json.starts_with('[') && json.contains("\"sorter\": \"merge\""));
+
+        let config = BenchConfig {
+            sorters: vec!["no_such_sort".to_string()],
+            ..config
+        };
+        assert_eq!(run(&config).unwrap_err(), "no_such_sort");
+    }
+}
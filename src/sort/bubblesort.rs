@@ -1,7 +1,7 @@
 use crate::sort::Sorter;
 use std::fmt::Debug;
 
-struct BubbleSort;
+pub struct BubbleSort;
 impl Sorter for BubbleSort {
     // [unsorted | sorted]
     // sorted: sink+1..slice.len()
@@ -26,7 +26,7 @@ impl Sorter for BubbleSort {
     }
 }
 
-struct BubbleSortWithSinkAtTheBeginning;
+pub struct BubbleSortWithSinkAtTheBeginning;
 
 impl Sorter for BubbleSortWithSinkAtTheBeginning {
     // [sorted | unsorted]
@@ -51,7 +51,7 @@ impl Sorter for BubbleSortWithSinkAtTheBeginning {
     }
 }
 
-struct BubbleSortDescending;
+pub struct BubbleSortDescending;
 impl Sorter for BubbleSortDescending {
     // [unsorted | sorted ]
     // sorted: sink+1..len()-1
@@ -1,9 +1,9 @@
 use crate::sort::Sorter;
 use std::fmt::Debug;
 
-struct SelectionSort;
+pub struct HeapSort;
 
-impl Sorter for SelectionSort {
+impl Sorter for HeapSort {
     fn sort<T: Ord + Debug>(slice: &mut [T]) {
         // 1. build max heap
         build(slice);
@@ -69,7 +69,7 @@ fn build_works() {
 }
 
 #[test]
-fn test_selection_sort() {
+fn test_heap_sort() {
     use crate::sort::tests::test_sorting;
-    test_sorting::<SelectionSort>();
+    test_sorting::<HeapSort>();
 }
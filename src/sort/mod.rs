@@ -1,3 +1,4 @@
+pub mod bench;
 pub mod bubblesort;
 pub mod heapsort;
 pub mod insertionsort;
@@ -6,7 +7,7 @@ pub mod quicksort;
 pub mod selectionsort;
 use std::fmt::Debug;
 
-trait Sorter {
+pub trait Sorter {
     fn sort<T: Ord + Debug + Clone>(slice: &mut [T]);
 }
 
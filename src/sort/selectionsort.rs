@@ -1,7 +1,7 @@
 use crate::sort::Sorter;
 use std::fmt::Debug;
 
-struct SelectionSort;
+pub struct SelectionSort;
 
 impl Sorter for SelectionSort {
     // [sorted | unstorted]
@@ -1,59 +1,49 @@
 use crate::data::graph::{Graph, GraphNode, GraphNodeIndex};
-use std::{
-    collections::{HashSet, VecDeque},
-    rc::Rc,
-};
+use crate::data::visit::{Neighbors, VisitMap};
+use std::{collections::VecDeque, rc::Rc};
 
-/// Traverse whole graph (by visiting all nodes) by using Breadth First Search.
-fn bfs_graph_traversal_in_indexed_graph<N, E>(
-    graph: &Graph<N, E>,
-    start: GraphNodeIndex,
-) -> Vec<GraphNodeIndex> {
+/// Traverse all nodes reachable from start node in graph of any representation (see data::visit)
+/// by using Breadth First Search.
+pub fn bfs_graph_traversal<G: Neighbors>(graph: &G, start: G::NodeId) -> Vec<G::NodeId> {
     let mut fifo = VecDeque::new();
     let mut output = Vec::new();
-    let mut seen = vec![false; graph.nodes.len()];
+    let mut seen: G::Map<()> = graph.visit_map();
+    if !graph.contains_node(&start) {
+        return output;
+    }
 
+    seen.insert(start.clone(), ());
+    output.push(start.clone());
     fifo.push_back(start);
-    seen[start] = true;
-    output.push(start);
 
     while let Some(current) = fifo.pop_front() {
-        for &(neighbour, _) in &graph.edges[current] {
-            if !seen[neighbour] {
-                fifo.push_back(neighbour);
-                seen[neighbour] = true;
-                output.push(neighbour);
-            }
-        }
-    }
-
-    output
-}
-
-fn bfs_graph_traversal_in_linked_graph(
-    start_node: Rc<GraphNode<char, ()>>,
-) -> Vec<Rc<GraphNode<char, ()>>> {
-    let mut output = Vec::new();
-    let mut fifo = VecDeque::new();
-    let mut seen = HashSet::new();
-
-    fifo.push_back(start_node.clone());
-    seen.insert(start_node.clone());
-    output.push(start_node.clone());
-
-    while let Some(node) = fifo.pop_front() {
-        for (neighbour, _edge) in node.neighbours.borrow().iter() {
+        graph.for_each_neighbour(&current, |neighbour, _| {
             if !seen.contains(neighbour) {
-                seen.insert(neighbour.clone());
                 fifo.push_back(neighbour.clone());
+                seen.insert(neighbour.clone(), ());
                 output.push(neighbour.clone());
             }
-        }
+        });
     }
 
     output
 }
 
+/// Traverse whole graph (by visiting all nodes) by using Breadth First Search.
+pub fn bfs_graph_traversal_in_indexed_graph<N, E>(
+    graph: &Graph<N, E>,
+    start: GraphNodeIndex,
+) -> Vec<GraphNodeIndex> {
+    bfs_graph_traversal(graph, start)
+}
+
+/// Traverse all nodes reachable from start node in linked graph by using Breadth First Search.
+pub fn bfs_graph_traversal_in_linked_graph<V, E>(
+    start_node: Rc<GraphNode<V, E>>,
+) -> Vec<Rc<GraphNode<V, E>>> {
+    bfs_graph_traversal(&start_node.clone(), start_node)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::data::graph::{
@@ -87,7 +77,7 @@ mod tests {
 
     #[test]
     fn bfs_graph_node_traversal_works() {
-        let (graph_node, _) = make_test_unweighted_linked_graph_nodes('a', None);
+        let (_graph, graph_node, _) = make_test_unweighted_linked_graph_nodes('a', None);
         let bfs_from_a_indexes = bfs_graph_traversal_in_linked_graph(graph_node);
 
         assert_eq!(bfs_from_a_indexes.len(), 8);
@@ -1,27 +1,29 @@
-use crate::data::graph::{Graph, GraphNodeIndex};
+use crate::data::visit::{Neighbors, VisitMap};
 
-/// Traverse whole graph (by visiting all nodes) by using Depth First Search.
-pub fn dfs_graph_traversal<V, E>(
-    graph: &Graph<V, E>,
-    start: GraphNodeIndex,
-) -> Vec<GraphNodeIndex> {
+/// Traverse all nodes reachable from start node (whole connected graph) by using Depth First Search,
+/// in graph of any representation (see data::visit).
+pub fn dfs_graph_traversal<G: Neighbors>(graph: &G, start: G::NodeId) -> Vec<G::NodeId> {
     let mut output = Vec::new();
     let mut lifo = Vec::new();
-    let mut seen = vec![false; graph.nodes.len()];
+    let mut seen: G::Map<()> = graph.visit_map();
+    if !graph.contains_node(&start) {
+        return output;
+    }
 
     lifo.push(start);
 
     while let Some(current) = lifo.pop() {
-        if !seen[current] {
-            seen[current] = true;
-            output.push(current);
+        if seen.contains(&current) {
+            continue;
         }
+        seen.insert(current.clone(), ());
+        output.push(current.clone());
 
-        for &(neighbour, _) in &graph.edges[current] {
-            if !seen[neighbour] {
-                lifo.push(neighbour);
+        graph.for_each_neighbour(&current, |neighbour, _| {
+            if !seen.contains(neighbour) {
+                lifo.push(neighbour.clone());
             }
-        }
+        });
     }
 
     output
@@ -29,7 +31,7 @@ pub fn dfs_graph_traversal<V, E>(
 
 #[cfg(test)]
 mod tests {
-    use crate::data::graph::make_test_unweighted_indexed_graph;
+    use crate::data::graph::{make_test_unweighted_indexed_graph, GraphNodeIndex};
 
     use super::*;
     use std::collections::HashSet;